@@ -24,7 +24,11 @@ echo ""
 echo "Deploying to Linera (publish + create)..."
 echo ""
 
-linera publish-and-create "$CONTRACT_WASM" "$SERVICE_WASM"
+# Self-claimed achievements and self-reported score are disabled unless enabled here.
+# The argument sets the initial admin (null = the creating signer) and oracle set.
+linera publish-and-create "$CONTRACT_WASM" "$SERVICE_WASM" \
+  --json-parameters '{"allow_self_claimed_achievements": false, "allow_self_reported_score": false, "transfer_policy": "Soulbound", "mint_policy": "Open", "limits": {"max_uri_length": 256, "max_achievements_per_update": 100, "max_achievements": 500}, "staking": {"min_stake": "100", "unbonding_delay_secs": 604800}}' \
+  --json-argument '{"admin": null, "oracles": []}'
 
 echo ""
 echo "================================================"
@@ -35,4 +35,4 @@ echo ""
 
 export PASSPORT_AGENT_CONFIG="$(pwd)/config.toml"
 export RUST_LOG=info
-~/linera-protocol/examples/target/release/passport_oracle
+~/linera-protocol/examples/target/release/passport_oracle --daemon
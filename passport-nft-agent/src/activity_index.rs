@@ -0,0 +1,333 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use linera_base::{
+    data_types::BlockHeight,
+    identifiers::{AccountOwner, ChainId},
+};
+
+use crate::{
+    chain_client::{ChainClient, IndexedOperation, OperationHistory},
+    scoring::ActivityAggregates,
+    store::{AgentStore, ChainCheckpoint},
+};
+
+/// Incremental indexer turning chain operations into persisted per-owner aggregates.
+///
+/// Each chain is read only above its stored checkpoint, and every operation is
+/// folded into the aggregates of the account that signed its block. Scoring then
+/// reads the aggregates instead of refetching history, so the cost of a cycle
+/// grows with new activity rather than with total history.
+#[derive(Clone)]
+pub struct ActivityIndex {
+    client: ChainClient,
+    store: AgentStore,
+}
+
+/// What a single `sync_chain` call did.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChainSyncReport {
+    pub new_operations: usize,
+    pub checkpoint: Option<BlockHeight>,
+    pub complete: bool,
+}
+
+impl ActivityIndex {
+    pub fn new(client: ChainClient, store: AgentStore) -> Self {
+        Self { client, store }
+    }
+
+    /// Folds every operation above the chain's checkpoint into the owner aggregates.
+    pub async fn sync_chain(&self, chain_id: &ChainId) -> Result<ChainSyncReport> {
+        let previous = self.store.checkpoint(chain_id)?;
+        let history = self
+            .client
+            .operation_history(chain_id, previous.map(|checkpoint| checkpoint.height))
+            .await?;
+        self.apply_history(chain_id, previous, history).await
+    }
+
+    /// Folds `history`, read above the `previous` checkpoint, and moves the checkpoint.
+    async fn apply_history(
+        &self,
+        chain_id: &ChainId,
+        previous: Option<ChainCheckpoint>,
+        history: OperationHistory,
+    ) -> Result<ChainSyncReport> {
+        // Operations whose block could not be resolved carry no timestamp. They and
+        // everything above them stay unprocessed, so the next sync retries them.
+        let unresolved_floor = history
+            .operations
+            .iter()
+            .filter(|operation| operation.timestamp.is_none())
+            .map(|operation| operation.height)
+            .min();
+        let operations: Vec<IndexedOperation> = history
+            .operations
+            .into_iter()
+            .filter(|operation| unresolved_floor.map_or(true, |floor| operation.height < floor))
+            .collect();
+
+        let Some(head) = operations.iter().map(|operation| operation.height).max() else {
+            return Ok(ChainSyncReport {
+                new_operations: 0,
+                checkpoint: previous.map(|checkpoint| checkpoint.height),
+                complete: previous.map_or(true, |checkpoint| checkpoint.complete),
+            });
+        };
+
+        // The paging cap only drops the oldest operations, so a truncated read
+        // leaves a permanent gap: remember it instead of silently forgetting.
+        let truncated = !history.complete && unresolved_floor.is_none();
+        let checkpoint = ChainCheckpoint {
+            height: head,
+            complete: previous.map_or(true, |checkpoint| checkpoint.complete) && !truncated,
+        };
+
+        let new_operations = operations.len();
+        let mut deltas: BTreeMap<AccountOwner, ActivityAggregates> = BTreeMap::new();
+        let mut leads = BTreeSet::new();
+        for operation in operations {
+            leads.extend(operation.self_transfer_chain());
+            if let Some(event) = operation.into_activity_event() {
+                deltas.entry(event.owner).or_default().fold(&event);
+            }
+        }
+
+        // Signers moving funds to their own account on another chain lead
+        // discovery there; the next cycle sweeps it.
+        self.store.record_lead_chains(&leads)?;
+        self.store
+            .apply_chain_batch(chain_id, checkpoint, &deltas)
+            .await?;
+
+        tracing::debug!(
+            chain_id = %chain_id,
+            new_operations = new_operations,
+            owners = deltas.len(),
+            checkpoint = %head,
+            "Chain activity indexed"
+        );
+
+        Ok(ChainSyncReport {
+            new_operations,
+            checkpoint: Some(head),
+            complete: checkpoint.complete,
+        })
+    }
+
+    /// Aggregates of `owner` summed over `chain_ids`, plus whether every one of
+    /// those chains has been indexed without gaps.
+    pub fn owner_aggregates(
+        &self,
+        owner: &AccountOwner,
+        chain_ids: &[ChainId],
+    ) -> Result<(ActivityAggregates, bool)> {
+        let mut aggregates = ActivityAggregates::default();
+        let mut complete = true;
+        for chain_id in chain_ids {
+            aggregates.merge(&self.store.aggregates(owner, chain_id)?);
+            complete &= self
+                .store
+                .checkpoint(chain_id)?
+                .is_some_and(|checkpoint| checkpoint.complete);
+        }
+        Ok((aggregates, complete))
+    }
+
+    /// Current checkpoints of `chain_ids`, for recording what a score was based on.
+    pub fn checkpoints(&self, chain_ids: &[ChainId]) -> Result<BTreeMap<ChainId, BlockHeight>> {
+        let mut checkpoints = BTreeMap::new();
+        for chain_id in chain_ids {
+            if let Some(checkpoint) = self.store.checkpoint(chain_id)? {
+                checkpoints.insert(*chain_id, checkpoint.height);
+            }
+        }
+        Ok(checkpoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use linera_base::{
+        crypto::CryptoHash,
+        data_types::{Amount, Timestamp},
+        identifiers::{Account, ApplicationId},
+    };
+    use linera_execution::{Operation, SystemOperation};
+
+    use super::*;
+
+    const CHAIN: &str = "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65";
+    const OWNER: &str = "0x5487b70625ce71f7ee29154ad32aefa1c526cb483bdb783dea2e1d17bc497844";
+    const HASH: &str = "f1b3b8a0a6d1e5c4a87cf3b2c0d9e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0";
+    const OTHER_CHAIN: &str = "a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1";
+
+    fn new_index() -> ActivityIndex {
+        let client = ChainClient::new("http://localhost:8080", "http://localhost:8081/operations");
+        ActivityIndex::new(client, AgentStore::temporary().unwrap())
+    }
+
+    fn chain_id() -> ChainId {
+        ChainId::from_str(CHAIN).unwrap()
+    }
+
+    fn owner() -> AccountOwner {
+        AccountOwner::from_str(OWNER).unwrap()
+    }
+
+    /// A user operation at `height`; `None` marks a block the indexer could not resolve.
+    fn operation(height: u64, day: Option<u64>) -> IndexedOperation {
+        IndexedOperation {
+            chain_id: chain_id(),
+            height: BlockHeight(height),
+            index: 0,
+            block: CryptoHash::from_str(HASH).unwrap(),
+            signer: Some(owner()),
+            timestamp: day.map(|day| Timestamp::from(day * 86_400 * 1_000_000)),
+            content: Operation::User {
+                application_id: ApplicationId::from_str(HASH).unwrap(),
+                bytes: Vec::new(),
+            },
+        }
+    }
+
+    /// A transfer at `height` from the signer to `recipient` on `chain`.
+    fn transfer(height: u64, chain: &str, recipient: AccountOwner) -> IndexedOperation {
+        IndexedOperation {
+            content: Operation::System(Box::new(SystemOperation::Transfer {
+                owner: owner(),
+                recipient: Account {
+                    chain_id: ChainId::from_str(chain).unwrap(),
+                    owner: recipient,
+                },
+                amount: Amount::ONE,
+            })),
+            ..operation(height, Some(1))
+        }
+    }
+
+    fn history(operations: Vec<IndexedOperation>, complete: bool) -> OperationHistory {
+        OperationHistory {
+            chain_id: chain_id(),
+            operations,
+            complete,
+        }
+    }
+
+    fn folded(index: &ActivityIndex) -> u64 {
+        index
+            .store
+            .aggregates(&owner(), &chain_id())
+            .unwrap()
+            .actions
+            .get("user_operation")
+            .map_or(0, |tally| tally.count)
+    }
+
+    async fn sync(index: &ActivityIndex, history: OperationHistory) -> ChainSyncReport {
+        let previous = index.store.checkpoint(&chain_id()).unwrap();
+        index
+            .apply_history(&chain_id(), previous, history)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn checkpoint_moves_to_the_newest_operation() {
+        let index = new_index();
+        let operations = vec![
+            operation(3, Some(2)),
+            operation(2, Some(1)),
+            operation(1, Some(1)),
+        ];
+        let report = sync(&index, history(operations, true)).await;
+
+        assert_eq!(report.new_operations, 3);
+        assert_eq!(report.checkpoint, Some(BlockHeight(3)));
+        assert!(report.complete);
+        assert_eq!(folded(&index), 3);
+
+        // A later read above the checkpoint only adds the new operations.
+        let report = sync(&index, history(vec![operation(4, Some(3))], true)).await;
+        assert_eq!(report.checkpoint, Some(BlockHeight(4)));
+        assert_eq!(folded(&index), 4);
+    }
+
+    #[tokio::test]
+    async fn unresolved_block_holds_the_checkpoint_below_it() {
+        let index = new_index();
+        let operations = vec![
+            operation(4, Some(2)),
+            operation(3, None),
+            operation(2, Some(1)),
+            operation(1, Some(1)),
+        ];
+        let report = sync(&index, history(operations, false)).await;
+
+        // Height 4 is resolved but sits above the gap, so it waits for the retry.
+        assert_eq!(report.new_operations, 2);
+        assert_eq!(report.checkpoint, Some(BlockHeight(2)));
+        assert!(report.complete, "an unresolved block is retried, not a gap");
+        assert_eq!(folded(&index), 2);
+
+        let operations = vec![operation(4, Some(2)), operation(3, Some(2))];
+        let report = sync(&index, history(operations, true)).await;
+        assert_eq!(report.checkpoint, Some(BlockHeight(4)));
+        assert!(report.complete);
+        assert_eq!(folded(&index), 4);
+    }
+
+    #[tokio::test]
+    async fn nothing_resolved_keeps_the_previous_checkpoint() {
+        let index = new_index();
+        sync(&index, history(vec![operation(1, Some(1))], true)).await;
+
+        let report = sync(&index, history(vec![operation(2, None)], false)).await;
+        assert_eq!(report.new_operations, 0);
+        assert_eq!(report.checkpoint, Some(BlockHeight(1)));
+        assert!(report.complete);
+        assert_eq!(folded(&index), 1);
+
+        let empty = new_index();
+        let report = sync(&empty, history(Vec::new(), true)).await;
+        assert_eq!(report.checkpoint, None);
+        assert!(empty.store.checkpoint(&chain_id()).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn truncated_history_stays_incomplete() {
+        let index = new_index();
+        let operations = vec![operation(10, Some(5)), operation(9, Some(5))];
+        let report = sync(&index, history(operations, false)).await;
+        assert_eq!(report.checkpoint, Some(BlockHeight(10)));
+        assert!(!report.complete);
+
+        // Reading the rest of the chain in full does not fill the skipped range.
+        let report = sync(&index, history(vec![operation(11, Some(6))], true)).await;
+        assert_eq!(report.checkpoint, Some(BlockHeight(11)));
+        assert!(!report.complete);
+
+        let (_, complete) = index.owner_aggregates(&owner(), &[chain_id()]).unwrap();
+        assert!(!complete);
+    }
+
+    #[tokio::test]
+    async fn transfers_to_own_account_lead_to_other_chains() {
+        let index = new_index();
+        let operations = vec![
+            transfer(3, OTHER_CHAIN, owner()),
+            transfer(2, CHAIN, owner()),
+            transfer(1, HASH, AccountOwner::CHAIN),
+        ];
+        sync(&index, history(operations, true)).await;
+
+        let other_chain = ChainId::from_str(OTHER_CHAIN).unwrap();
+        assert_eq!(
+            index.store.lead_chains().unwrap(),
+            BTreeSet::from([other_chain])
+        );
+    }
+}
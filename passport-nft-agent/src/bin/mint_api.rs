@@ -39,6 +39,9 @@ struct MintRequest {
 #[derive(Debug, Serialize)]
 struct MintResponse {
     success: bool,
+    /// Always `"forwarded"`: the mint is only queued in a block, and off the creator
+    /// chain it waits for the creator's approval before the passport exists.
+    status: &'static str,
     token_id: Vec<u8>,
     owner: String,
     metadata_uri: String,
@@ -118,6 +121,7 @@ async fn mint_passport_handler(
         metadata_uri: metadata_uri.clone(),
         image_uri: image_uri.clone(),
         content_hash: content_hash.clone(),
+        owner: None,
     };
 
     // Submit mint operation to blockchain
@@ -127,11 +131,12 @@ async fn mint_passport_handler(
             info!(
                 owner = %request.owner,
                 token_id = ?token_id_bytes,
-                "Mint operation submitted successfully"
+                "Mint operation forwarded"
             );
 
             Ok(AxumJson(MintResponse {
                 success: true,
+                status: "forwarded",
                 token_id: token_id_bytes,
                 owner: request.owner,
                 metadata_uri,
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use axum::{
-    extract::Query,
+    extract::{Query, State},
     http::StatusCode,
     response::Json,
     routing::get,
@@ -9,10 +9,11 @@ use axum::{
 use passport_nft_agent::{
     chain_client::{ChainClient, PassportInfo},
     config::AppConfig,
+    rules::RuleSet,
     scoring::{self, ObservationContext},
 };
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 use tracing::{info, debug};
 use tower_http::cors::CorsLayer;
 
@@ -28,6 +29,8 @@ struct ScoreResponse {
     achievements: Vec<Achievement>,
     method: String,
     processing_time_ms: u128,
+    /// false if the indexer history was cut at `indexer_max_operations`
+    history_complete: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,6 +41,7 @@ struct Achievement {
 }
 
 async fn calculate_quick_score(
+    State(rules): State<Arc<RuleSet>>,
     Query(params): Query<ScoreRequest>,
 ) -> Result<Json<ScoreResponse>, StatusCode> {
     let start = Instant::now();
@@ -53,7 +57,9 @@ async fn calculate_quick_score(
     let client = ChainClient::new(
         &config.graphql_endpoint,
         &config.indexer_endpoint,
-    );
+    )
+    .with_paging(config.indexer_page_size, config.indexer_max_operations)
+    .with_max_concurrent_requests(config.max_concurrent_requests);
 
     // 2. Найти passport по owner
     let all_passports = client.all_passports().await.map_err(|e| {
@@ -62,6 +68,10 @@ async fn calculate_quick_score(
     })?;
 
     debug!("Found {} passports total", all_passports.len());
+    let total_supply = client.total_supply().await.map_err(|e| {
+        tracing::error!("Failed to fetch total supply: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
     for (i, p) in all_passports.iter().enumerate() {
         debug!("Passport {}: owner={}, owner_chain={}", i, p.owner, p.owner_chain);
     }
@@ -97,8 +107,9 @@ async fn calculate_quick_score(
     let activity = client.owner_activity(&owner_parsed, &owner_chain)
         .await
         .unwrap_or_default();
+    let history_complete = activity.complete;
 
-    debug!("Retrieved {} activity events", activity.len());
+    debug!("Retrieved {} activity events", activity.events.len());
 
     // 4. Создать контекст
     let fake_passport = passport_opt.unwrap_or_else(|| PassportInfo {
@@ -107,19 +118,20 @@ async fn calculate_quick_score(
         },
         owner: params.owner.clone(),
         owner_chain: owner_chain.to_string(),
+        created_at: None,
+        transfers: None,
         achievements: Some(vec![]),
         score: Some(0),
+        revision: Some(0),
+        pending_score: None,
+        pending_update: None,
     });
 
-    let context = ObservationContext::from_passport(fake_passport, activity);
+    let context = ObservationContext::from_passport(fake_passport, activity.events)
+        .with_total_supply(total_supply);
 
     // 5. Rule-based скоринг
-    let achievement_result = scoring::evaluate_rules(&config, &context)
-        .await
-        .map_err(|e| {
-            tracing::error!("Scoring error: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let achievement_result = scoring::apply_rules(&rules, &context);
 
     let processing_time = start.elapsed().as_millis();
 
@@ -145,6 +157,7 @@ async fn calculate_quick_score(
             .collect(),
         method: "rule-based".to_string(),
         processing_time_ms: processing_time,
+        history_complete,
     }))
 }
 
@@ -152,6 +165,9 @@ async fn calculate_quick_score(
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
 
+    // Rules are validated and parsed once, not on every request.
+    let config = AppConfig::from_sources()?;
+    let rules = Arc::new(RuleSet::load(&config.rules_path).await?);
 
     // Configure CORS to allow frontend requests
     let cors = CorsLayer::new()
@@ -161,6 +177,7 @@ async fn main() -> Result<()> {
 
     let app = Router::new()
         .route("/quick-score", get(calculate_quick_score))
+        .with_state(rules)
         .layer(cors);
 
     let port = std::env::var("QUICK_SCORE_PORT").unwrap_or_else(|_| "8001".to_string());
@@ -1,10 +1,21 @@
+use futures::stream::{self, StreamExt};
 use graphql_client::{GraphQLQuery, Response};
 use hex;
 use reqwest::{Client, Url};
 use serde::Deserialize;
-use std::{str::FromStr, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Semaphore;
 
-use linera_base::identifiers::{AccountOwner, ChainId};
+use linera_base::{
+    crypto::CryptoHash,
+    data_types::{BlockHeight, Timestamp},
+    identifiers::{AccountOwner, ChainId},
+};
 use linera_execution::{Operation, SystemOperation};
 use linera_indexer_graphql_client::operations::{
     self,
@@ -13,11 +24,130 @@ use linera_indexer_graphql_client::operations::{
 
 use crate::scoring::OwnerActivityEvent;
 
+/// Default number of operations requested per indexer page.
+pub const DEFAULT_PAGE_SIZE: u32 = 500;
+/// Default upper bound on operations walked back per chain and query.
+pub const DEFAULT_MAX_OPERATIONS: usize = 50_000;
+/// Default number of indexer requests allowed in flight at once.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
 #[derive(Debug, Clone)]
 pub struct ChainClient {
     http: Client,
     graphql_endpoint: String,
     indexer_endpoint: Url,
+    /// Root of the indexer, serving `block` queries next to the `operations` plugin
+    indexer_root: Url,
+    page_size: u32,
+    max_operations: usize,
+    max_concurrent_requests: usize,
+    /// Shared by all clones, so the limit holds across chains and passports.
+    request_permits: Arc<Semaphore>,
+}
+
+/// Operations read from the indexer for one chain, newest first.
+#[derive(Debug, Clone)]
+pub struct OperationHistory {
+    pub chain_id: ChainId,
+    pub operations: Vec<IndexedOperation>,
+    /// `false` when paging stopped at `max_operations` before reaching the
+    /// start of the chain (or the requested checkpoint), or when some blocks
+    /// could not be resolved to a signer.
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexedOperation {
+    pub chain_id: ChainId,
+    pub height: BlockHeight,
+    pub index: u64,
+    pub block: CryptoHash,
+    /// Authenticated signer of the block containing the operation.
+    pub signer: Option<AccountOwner>,
+    pub timestamp: Option<Timestamp>,
+    pub content: Operation,
+}
+
+/// Subset of a block header needed to attribute its operations.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeaderInfo {
+    pub timestamp: Timestamp,
+    pub authenticated_signer: Option<AccountOwner>,
+}
+
+impl IndexedOperation {
+    /// Converts the operation into an activity event credited to the block signer.
+    ///
+    /// Operations in blocks without an authenticated signer, and operations that do
+    /// not count as activity, yield `None`.
+    pub fn into_activity_event(self) -> Option<OwnerActivityEvent> {
+        let signer = self.signer?;
+        let event = match self.content {
+            Operation::System(op) => match *op {
+                SystemOperation::Transfer {
+                    amount, recipient, ..
+                } => OwnerActivityEvent::system_transfer(
+                    signer,
+                    self.chain_id,
+                    self.height,
+                    self.index,
+                    amount,
+                    recipient.owner,
+                ),
+                // SECURITY FIX: Track app creation for APP_CREATOR achievement
+                SystemOperation::CreateApplication { module_id, .. } => {
+                    OwnerActivityEvent::create_application(
+                        signer,
+                        self.chain_id,
+                        self.height,
+                        self.index,
+                        module_id.to_string(),
+                    )
+                }
+                _ => return None,
+            },
+            Operation::User {
+                application_id,
+                bytes,
+            } => OwnerActivityEvent::user_operation(
+                signer,
+                self.chain_id,
+                self.height,
+                self.index,
+                application_id,
+                bytes,
+            ),
+        };
+        Some(match self.timestamp {
+            Some(timestamp) => event.at(timestamp),
+            None => event,
+        })
+    }
+
+    /// Chain that received a transfer from the block signer to their own account
+    /// there, a sign that the signer uses that chain too.
+    pub fn self_transfer_chain(&self) -> Option<ChainId> {
+        let signer = self.signer?;
+        let Operation::System(op) = &self.content else {
+            return None;
+        };
+        match &**op {
+            SystemOperation::Transfer { recipient, .. }
+                if recipient.owner == signer && recipient.chain_id != self.chain_id =>
+            {
+                Some(recipient.chain_id)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Activity attributed to one owner, with the completeness of the underlying history.
+#[derive(Debug, Clone, Default)]
+pub struct OwnerActivity {
+    pub events: Vec<OwnerActivityEvent>,
+    pub complete: bool,
 }
 
 impl ChainClient {
@@ -26,14 +156,42 @@ impl ChainClient {
             .timeout(Duration::from_secs(30))
             .build()
             .expect("failed to build HTTP client");
+        let indexer_endpoint =
+            Url::parse(&indexer_endpoint.into()).expect("invalid indexer operations endpoint");
+        let indexer_root = indexer_endpoint
+            .join("./")
+            .expect("indexer operations endpoint has a base URL");
         Self {
             http,
             graphql_endpoint: graphql_endpoint.into(),
-            indexer_endpoint: Url::parse(&indexer_endpoint.into())
-                .expect("invalid indexer operations endpoint"),
+            indexer_endpoint,
+            indexer_root,
+            page_size: DEFAULT_PAGE_SIZE,
+            max_operations: DEFAULT_MAX_OPERATIONS,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            request_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS)),
         }
     }
 
+    /// Overrides the indexer page size and the per-chain cap on walked operations.
+    pub fn with_paging(mut self, page_size: u32, max_operations: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self.max_operations = max_operations.max(1);
+        self
+    }
+
+    /// Bounds the number of indexer requests in flight across every clone of this client.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self.request_permits = Arc::new(Semaphore::new(self.max_concurrent_requests));
+        self
+    }
+
+    /// Number of requests worth running concurrently; used to size buffered streams.
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
     pub async fn all_passports(&self) -> Result<Vec<PassportInfo>, reqwest::Error> {
         let query = serde_json::json!({
             "query": r#"
@@ -42,8 +200,16 @@ impl ChainClient {
                     tokenId { id }
                     owner
                     ownerChain
-                    achievements
+                    createdAt
+                    transfers { at }
+                    achievements { code points issuer approvedBy evidenceHash }
                     score
+                    revision
+                    pendingScore
+                    pendingUpdate {
+                        achievements { code points issuer approvedBy evidenceHash }
+                        dispute { reason }
+                    }
                 }
             }
             "#
@@ -58,6 +224,49 @@ impl ChainClient {
         Ok(data.data.all_passports)
     }
 
+    /// Number of passports minted on the chain `graphql_endpoint` points at.
+    pub async fn total_supply(&self) -> Result<u64, reqwest::Error> {
+        let query = serde_json::json!({ "query": "{ totalSupply }" });
+        let resp = self
+            .http
+            .post(&self.graphql_endpoint)
+            .json(&query)
+            .send()
+            .await?;
+        let data: GraphQlResponse<TotalSupplyData> = resp.json().await?;
+        Ok(data.data.total_supply)
+    }
+
+    /// Quorum proposals still waiting for votes on a passport. They live on the
+    /// application creator chain, which `graphql_endpoint` points at.
+    pub async fn pending_proposals(
+        &self,
+        token_id: &[u8],
+    ) -> Result<Vec<ProposalInfo>, anyhow::Error> {
+        let query = serde_json::json!({
+            "query": r#"
+            query PendingProposals($tokenId: TokenIdInput!) {
+                pendingProposals(tokenId: $tokenId) {
+                    revision
+                    approvals
+                    expiresAt
+                }
+            }
+            "#,
+            "variables": {
+                "tokenId": { "id": token_id },
+            },
+        });
+        let resp = self
+            .http
+            .post(&self.graphql_endpoint)
+            .json(&query)
+            .send()
+            .await?;
+        let data: GraphQlResponse<PendingProposalsData> = resp.json().await?;
+        Ok(data.data.pending_proposals)
+    }
+
     pub async fn passport_by_bytes(
         &self,
         token_id: &[u8],
@@ -75,18 +284,147 @@ impl ChainClient {
         }))
     }
 
-    pub async fn owner_activity(
+    /// Walks the chain's operation history backwards, one page at a time, and
+    /// resolves the signer and timestamp of every block it touches.
+    ///
+    /// Paging stops at the first operation at or below `since` (a checkpoint that
+    /// was already processed), at the start of the chain, or after `max_operations`.
+    pub async fn operation_history(
         &self,
-        owner: &AccountOwner,
         chain_id: &ChainId,
-    ) -> Result<Vec<OwnerActivityEvent>, anyhow::Error> {
-        let variables = Variables {
-            from: OperationKeyKind::Last(*chain_id),
-            limit: Some(1000),
+        since: Option<BlockHeight>,
+    ) -> Result<OperationHistory, anyhow::Error> {
+        let mut operations = Vec::new();
+        let mut from = OperationKeyKind::Last(*chain_id);
+
+        let mut complete = loop {
+            let remaining = self.max_operations - operations.len();
+            let variables = Variables {
+                from,
+                limit: Some(remaining.min(self.page_size as usize) as i64),
+            };
+            let page = self.operations_page(variables).await?;
+
+            let mut reached_checkpoint = false;
+            let mut next = None;
+            for entry in page {
+                if since.is_some_and(|checkpoint| entry.key.height <= checkpoint) {
+                    reached_checkpoint = true;
+                    break;
+                }
+                next = entry.previous_operation;
+                operations.push(IndexedOperation {
+                    chain_id: entry.key.chain_id,
+                    height: entry.key.height,
+                    index: entry.key.index as u64,
+                    block: entry.block,
+                    signer: None,
+                    timestamp: None,
+                    content: entry.content,
+                });
+            }
+            if reached_checkpoint {
+                break true;
+            }
+
+            // An empty page or a missing previous key means we reached the first operation.
+            let Some(previous) = next else {
+                break true;
+            };
+
+            if operations.len() >= self.max_operations {
+                tracing::warn!(
+                    chain_id = %chain_id,
+                    max_operations = self.max_operations,
+                    "Operation history truncated at max_operations"
+                );
+                break false;
+            }
+            from = OperationKeyKind::Key(previous);
         };
 
+        let blocks: BTreeSet<CryptoHash> = operations.iter().map(|op| op.block).collect();
+        let lookups = stream::iter(blocks)
+            .map(|hash| async move { (hash, self.block_header(chain_id, hash).await) })
+            .buffered(self.max_concurrent_requests)
+            .collect::<Vec<_>>()
+            .await;
+        let mut headers = BTreeMap::new();
+        for (hash, lookup) in lookups {
+            match lookup {
+                Ok(header) => {
+                    headers.insert(hash, header);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        chain_id = %chain_id,
+                        block = %hash,
+                        error = %err,
+                        "Failed to resolve block signer, its operations are not attributed"
+                    );
+                    complete = false;
+                }
+            }
+        }
+        for operation in &mut operations {
+            if let Some(header) = headers.get(&operation.block) {
+                operation.signer = header.authenticated_signer;
+                operation.timestamp = Some(header.timestamp);
+            }
+        }
+
+        Ok(OperationHistory {
+            chain_id: *chain_id,
+            operations,
+            complete,
+        })
+    }
+
+    /// Reads the signer and timestamp of a block from the indexer.
+    pub async fn block_header(
+        &self,
+        chain_id: &ChainId,
+        hash: CryptoHash,
+    ) -> Result<BlockHeaderInfo, anyhow::Error> {
+        let query = serde_json::json!({
+            "query": r#"
+            query Block($hash: CryptoHash, $chainId: ChainId!) {
+                block(hash: $hash, chainId: $chainId) {
+                    block {
+                        header {
+                            timestamp
+                            authenticatedSigner
+                        }
+                    }
+                }
+            }
+            "#,
+            "variables": {
+                "hash": hash,
+                "chainId": chain_id,
+            },
+        });
+        let _permit = self.request_permits.acquire().await?;
+        let resp = self
+            .http
+            .post(self.indexer_root.clone())
+            .json(&query)
+            .send()
+            .await?;
+        let data: GraphQlResponse<BlockData> = resp.json().await?;
+        data.data
+            .block
+            .map(|value| value.block.header)
+            .ok_or_else(|| anyhow::anyhow!("block {hash} not found on chain {chain_id}"))
+    }
+
+    async fn operations_page(
+        &self,
+        variables: Variables,
+    ) -> Result<Vec<operations::operations::OperationsOperations>, anyhow::Error> {
         let request_body = operations::Operations::build_query(variables);
 
+        let _permit = self.request_permits.acquire().await?;
         let response = self
             .http
             .post(self.indexer_endpoint.clone())
@@ -109,67 +447,55 @@ impl ChainClient {
         let data = response_body
             .data
             .ok_or_else(|| anyhow::anyhow!("missing operations data"))?;
+        Ok(data.operations)
+    }
 
-        let mut events = Vec::new();
-
-        for entry in data.operations {
-            match entry.content {
-                Operation::System(op) => match *op {
-                    SystemOperation::Transfer {
-                        owner: op_owner,
-                        amount,
-                        recipient,
-                    } => {
-                        if op_owner == *owner {
-                            events.push(OwnerActivityEvent::system_transfer(
-                                *owner,
-                                entry.key.chain_id,
-                                entry.key.height,
-                                entry.key.index as u64,
-                                amount,
-                                recipient.owner,
-                            ));
-                        }
-                    }
-                    SystemOperation::CreateApplication { module_id, .. } => {
-                        // SECURITY FIX: Track app creation for APP_CREATOR achievement
-                        events.push(OwnerActivityEvent::create_application(
-                            *owner,
-                            entry.key.chain_id,
-                            entry.key.height,
-                            entry.key.index as u64,
-                            module_id.to_string(),
-                        ));
-                    }
-                    _ => {}
-                },
-                Operation::User {
-                    application_id,
-                    bytes,
-                } => {
-                    events.push(OwnerActivityEvent::user_operation(
-                        *owner,
-                        entry.key.chain_id,
-                        entry.key.height,
-                        entry.key.index as u64,
-                        application_id,
-                        bytes,
-                    ));
-                }
-            }
-        }
+    pub async fn owner_activity(
+        &self,
+        owner: &AccountOwner,
+        chain_id: &ChainId,
+    ) -> Result<OwnerActivity, anyhow::Error> {
+        self.owner_activity_since(owner, chain_id, None).await
+    }
 
-        Ok(events)
+    /// Activity of `owner` on `chain_id` newer than the `since` checkpoint.
+    ///
+    /// Only operations from blocks signed by `owner` are included, so other owners
+    /// of a shared chain do not contribute to this owner's reputation.
+    pub async fn owner_activity_since(
+        &self,
+        owner: &AccountOwner,
+        chain_id: &ChainId,
+        since: Option<BlockHeight>,
+    ) -> Result<OwnerActivity, anyhow::Error> {
+        let history = self.operation_history(chain_id, since).await?;
+        let events = history
+            .operations
+            .into_iter()
+            .filter(|operation| operation.signer.as_ref() == Some(owner))
+            .filter_map(IndexedOperation::into_activity_event)
+            .collect();
+
+        Ok(OwnerActivity {
+            events,
+            complete: history.complete,
+        })
     }
 
     /// CROSS-CHAIN FEATURE: Query activity across multiple chains for one owner
-    /// This aggregates reputation from all user's microchains
+    /// This aggregates reputation from all user's microchains.
+    /// Chains are queried concurrently (bounded by `max_concurrent_requests`) and
+    /// their events are appended in the order of `chain_ids`. The result is
+    /// complete only if every chain was read successfully and in full.
     pub async fn owner_activity_cross_chain(
         &self,
         owner: &AccountOwner,
         chain_ids: &[ChainId],
-    ) -> Result<Vec<OwnerActivityEvent>, anyhow::Error> {
-        let mut all_events = Vec::new();
+    ) -> Result<OwnerActivity, anyhow::Error> {
+        let mut aggregated = OwnerActivity {
+            events: Vec::new(),
+            complete: true,
+        };
 
         tracing::info!(
             owner = %owner,
@@ -177,15 +503,23 @@ impl ChainClient {
             "Fetching cross-chain activity for owner"
         );
 
-        for chain_id in chain_ids {
-            match self.owner_activity(owner, chain_id).await {
-                Ok(events) => {
+        let results = stream::iter(chain_ids)
+            .map(|chain_id| async move { (chain_id, self.owner_activity(owner, chain_id).await) })
+            .buffered(self.max_concurrent_requests)
+            .collect::<Vec<_>>()
+            .await;
+
+        for (chain_id, result) in results {
+            match result {
+                Ok(activity) => {
                     tracing::debug!(
                         chain_id = %chain_id,
-                        event_count = events.len(),
+                        event_count = activity.events.len(),
+                        complete = activity.complete,
                         "Fetched activity from chain"
                     );
-                    all_events.extend(events);
+                    aggregated.events.extend(activity.events);
+                    aggregated.complete &= activity.complete;
                 }
                 Err(err) => {
                     tracing::warn!(
@@ -194,29 +528,19 @@ impl ChainClient {
                         "Failed to fetch activity from chain, continuing with other chains"
                     );
                     // Continue with other chains even if one fails
+                    aggregated.complete = false;
                 }
             }
         }
 
         tracing::info!(
             owner = %owner,
-            total_events = all_events.len(),
+            total_events = aggregated.events.len(),
+            complete = aggregated.complete,
             "Cross-chain activity aggregated"
         );
 
-        Ok(all_events)
-    }
-
-    /// Get all chain IDs where this owner has activity
-    /// For demo, we'll query a configurable list of chains
-    pub async fn get_owner_chains(
-        &self,
-        _owner: &AccountOwner,
-        configured_chains: &[ChainId],
-    ) -> Result<Vec<ChainId>, anyhow::Error> {
-        // For hackathon demo: return configured chains to scan
-        // In production, this could query indexer for chains where owner has activity
-        Ok(configured_chains.to_vec())
+        Ok(aggregated)
     }
 }
 
@@ -225,12 +549,58 @@ struct GraphQlResponse<T> {
     data: T,
 }
 
+#[derive(Debug, Deserialize)]
+struct BlockData {
+    block: Option<BlockValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockValue {
+    block: BlockContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockContent {
+    header: BlockHeaderInfo,
+}
+
 #[derive(Debug, Deserialize)]
 struct AllPassportsData {
     #[serde(rename = "allPassports")]
     all_passports: Vec<PassportInfo>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TotalSupplyData {
+    #[serde(rename = "totalSupply")]
+    total_supply: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PendingProposalsData {
+    #[serde(rename = "pendingProposals")]
+    pending_proposals: Vec<ProposalInfo>,
+}
+
+/// Open quorum proposal, as returned by `pendingProposals`.
+#[derive(Debug, Deserialize)]
+pub struct ProposalInfo {
+    /// Passport revision the proposal applies to.
+    pub revision: u64,
+    /// Oracles that voted for it.
+    pub approvals: Vec<String>,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Timestamp,
+}
+
+impl ProposalInfo {
+    pub fn approved_by(&self, oracle: &AccountOwner) -> bool {
+        self.approvals
+            .iter()
+            .any(|approval| AccountOwner::from_str(approval).is_ok_and(|owner| owner == *oracle))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct PassportsData {
     passport: PassportRoot,
@@ -249,8 +619,49 @@ pub struct PassportInfo {
     pub owner: String,
     #[serde(rename = "ownerChain")]
     pub owner_chain: String,
-    pub achievements: Option<Vec<String>>,
+    /// Mint time, in microseconds since the Unix epoch.
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<u64>,
+    /// Ownership changes, oldest first.
+    pub transfers: Option<Vec<PassportTransfer>>,
+    pub achievements: Option<Vec<PassportAchievement>>,
     pub score: Option<u64>,
+    /// Counter the contract bumps on every oracle update.
+    pub revision: Option<u64>,
+    /// Score including an update still inside its challenge window.
+    #[serde(rename = "pendingScore", default)]
+    pub pending_score: Option<u64>,
+    /// Oracle update still inside its challenge window (optimistic mode).
+    #[serde(rename = "pendingUpdate", default)]
+    pub pending_update: Option<PendingUpdateInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PendingUpdateInfo {
+    #[serde(default)]
+    pub achievements: Vec<PassportAchievement>,
+    /// Set once the update has been disputed and awaits admin resolution.
+    pub dispute: Option<serde_json::Value>,
+}
+
+/// Ownership change of a passport; only its time is read.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PassportTransfer {
+    /// Microseconds since the Unix epoch.
+    pub at: u64,
+}
+
+/// On-chain achievement record, as returned by the passport GraphQL service.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PassportAchievement {
+    pub code: String,
+    pub points: u64,
+    pub issuer: String,
+    /// Oracles whose votes reached the quorum; empty for a single signature.
+    #[serde(default)]
+    pub approved_by: Vec<String>,
+    pub evidence_hash: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -270,6 +681,39 @@ impl PassportInfo {
     pub fn token_id_bytes(&self) -> anyhow::Result<Option<Vec<u8>>> {
         self.token_id.as_bytes()
     }
+
+    /// Codes of the achievements recorded on-chain, including those of an update
+    /// still inside its challenge window.
+    pub fn achievement_codes(&self) -> Vec<String> {
+        self.all_achievements()
+            .map(|achievement| achievement.code.clone())
+            .collect()
+    }
+
+    /// Score as it stands once a pending optimistic update is finalized.
+    pub fn effective_score(&self) -> u64 {
+        self.pending_score.or(self.score).unwrap_or(0)
+    }
+
+    /// Codes of finalized and pending achievements that `issuer` granted or voted for.
+    pub fn codes_issued_by(&self, issuer: &AccountOwner) -> BTreeSet<String> {
+        let is_issuer =
+            |owner: &String| AccountOwner::from_str(owner).is_ok_and(|owner| owner == *issuer);
+        self.all_achievements()
+            .filter(|achievement| {
+                is_issuer(&achievement.issuer) || achievement.approved_by.iter().any(is_issuer)
+            })
+            .map(|achievement| achievement.code.clone())
+            .collect()
+    }
+
+    fn all_achievements(&self) -> impl Iterator<Item = &PassportAchievement> {
+        let pending = self
+            .pending_update
+            .iter()
+            .flat_map(|pending| pending.achievements.iter());
+        self.achievements.iter().flatten().chain(pending)
+    }
 }
 
 impl PassportToken {
@@ -7,6 +7,15 @@ pub struct AppConfig {
     pub graphql_endpoint: String,
     #[serde(default = "default_indexer_endpoint")]
     pub indexer_endpoint: String,
+    /// Operations requested per indexer page
+    #[serde(default = "default_indexer_page_size")]
+    pub indexer_page_size: u32,
+    /// Hard cap on operations walked back per chain in a single query
+    #[serde(default = "default_indexer_max_operations")]
+    pub indexer_max_operations: usize,
+    /// Upper bound on concurrent indexer requests, and on passports scored in parallel
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
     pub wallet_path: String,
     #[serde(default = "default_storage_path")]
     pub storage_path: String,
@@ -18,9 +27,14 @@ pub struct AppConfig {
     pub rules_path: String,
     #[serde(default)]
     pub openai: Option<OpenAiConfig>,
-    /// CROSS-CHAIN FEATURE: Additional chains to scan for cross-chain reputation
+    /// CROSS-CHAIN FEATURE: Additional chains swept by the indexer. Activity on them is
+    /// credited only to the owners that signed it, via per-owner chain discovery
     #[serde(default)]
     pub cross_chain_ids: Vec<String>,
+    /// Optional JSON file mapping owners to the chains they control. Discovery cannot
+    /// find an owner chain that no swept chain leads to, so list such chains here
+    #[serde(default)]
+    pub owner_chains_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -64,6 +78,18 @@ fn default_openai_model() -> String {
     "gpt-4.1-mini".to_string()
 }
 
+fn default_indexer_page_size() -> u32 {
+    crate::chain_client::DEFAULT_PAGE_SIZE
+}
+
+fn default_indexer_max_operations() -> usize {
+    crate::chain_client::DEFAULT_MAX_OPERATIONS
+}
+
+fn default_max_concurrent_requests() -> usize {
+    crate::chain_client::DEFAULT_MAX_CONCURRENT_REQUESTS
+}
+
 fn default_indexer_endpoint() -> String {
     "http://127.0.0.1:8000/operations".to_string()
 }
@@ -0,0 +1,88 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+use anyhow::{Context, Result};
+use linera_base::identifiers::{AccountOwner, ChainId};
+
+use crate::{config::AppConfig, store::AgentStore};
+
+/// Finds the chains that belong to each passport owner.
+///
+/// Two sources are combined:
+/// - chains on which the activity index saw the owner sign blocks,
+/// - an operator-maintained `owner_chains_path` JSON file
+///   (`{"<owner>": ["<chain id>", ...]}`).
+///
+/// Discovery only covers chains the indexer sweeps: passport chains,
+/// `cross_chain_ids`, the chains in the mapping file, chains already found and
+/// chains that swept transfers lead to, where a signer sent tokens to their own
+/// account. Neither the indexer nor the chain exposes which chains an owner
+/// controls, so activity on a chain none of these reach is never credited.
+#[derive(Clone)]
+pub struct ChainDiscovery {
+    store: AgentStore,
+    static_chains: BTreeMap<AccountOwner, BTreeSet<ChainId>>,
+}
+
+impl ChainDiscovery {
+    pub fn new(store: AgentStore, static_chains: BTreeMap<AccountOwner, BTreeSet<ChainId>>) -> Self {
+        Self {
+            store,
+            static_chains,
+        }
+    }
+
+    /// Loads the mapping file named in `config`, if any.
+    pub async fn from_config(config: &AppConfig, store: AgentStore) -> Result<Self> {
+        let static_chains = match &config.owner_chains_path {
+            Some(path) => load_owner_chains_file(path).await?,
+            None => BTreeMap::new(),
+        };
+        Ok(Self::new(store, static_chains))
+    }
+
+    /// All chains known to belong to `owner`, including `owner_chain`.
+    pub fn owner_chains(&self, owner: &AccountOwner, owner_chain: ChainId) -> Result<Vec<ChainId>> {
+        let mut chains = self.store.owner_chains(owner)?;
+        if let Some(known) = self.static_chains.get(owner) {
+            chains.extend(known.iter().copied());
+        }
+        chains.remove(&owner_chain);
+
+        let mut ordered = vec![owner_chain];
+        ordered.extend(chains);
+        Ok(ordered)
+    }
+
+    /// Chains the indexer should sweep so discovery stays current.
+    pub fn candidate_chains(&self) -> Result<BTreeSet<ChainId>> {
+        let mut chains = self.store.discovered_chains()?;
+        chains.extend(self.store.lead_chains()?);
+        chains.extend(self.static_chains.values().flatten().copied());
+        Ok(chains)
+    }
+}
+
+async fn load_owner_chains_file(path: &str) -> Result<BTreeMap<AccountOwner, BTreeSet<ChainId>>> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("failed to read owner chains file {path}"))?;
+    let raw: BTreeMap<String, Vec<String>> = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse owner chains file {path}"))?;
+
+    let mut mapping = BTreeMap::new();
+    for (owner, chains) in raw {
+        let owner = AccountOwner::from_str(&owner)
+            .with_context(|| format!("invalid owner {owner} in {path}"))?;
+        let chains = chains
+            .iter()
+            .map(|chain| {
+                ChainId::from_str(chain).with_context(|| format!("invalid chain {chain} in {path}"))
+            })
+            .collect::<Result<BTreeSet<_>>>()?;
+        mapping.insert(owner, chains);
+    }
+    Ok(mapping)
+}
@@ -1,7 +1,12 @@
+pub mod activity_index;
 pub mod chain_client;
 pub mod config;
+pub mod discovery;
 pub mod llm;
+pub mod oracle;
+pub mod rules;
 pub mod scoring;
+pub mod store;
 pub mod updater;
 
 use anyhow::{anyhow, Result};
@@ -0,0 +1,529 @@
+use std::{collections::BTreeSet, path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use futures::stream::{self, StreamExt};
+use linera_base::identifiers::ChainId;
+use passport_nft::{error::PassportError, AchievementInput, TokenId, UpdateArgs};
+use tokio::sync::watch;
+
+use crate::{
+    activity_index::ActivityIndex,
+    chain_client::{ChainClient, PassportInfo},
+    config::AppConfig,
+    discovery::ChainDiscovery,
+    rules::RuleSet,
+    scoring::{self, ObservationContext},
+    store::{self, AgentStore, ChainView, PassportEpoch, PendingSubmission},
+    updater::PassportUpdater,
+};
+
+/// Attempts per update; retries are safe because updates carry an expected revision.
+const SUBMIT_ATTEMPTS: u32 = 3;
+const SUBMIT_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// How long an accepted update may take to reach the passport chain before it is
+/// recomputed and sent again. A duplicate is harmless: it carries the same expected
+/// revision, so at most one applies.
+const SETTLE_WINDOW_SECS: u64 = 600;
+
+/// Result of processing a single passport during a scan cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportOutcome {
+    UpToDate,
+    Submitted,
+    /// The vote was recorded, but the oracle quorum has not been reached yet.
+    Proposed,
+    DryRun,
+}
+
+/// Counters collected over one pass through `all_passports()`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CycleReport {
+    pub scanned: usize,
+    pub submitted: usize,
+    /// Votes that only recorded a quorum proposal.
+    pub proposed: usize,
+    pub up_to_date: usize,
+    pub failed: usize,
+    /// Set when a shutdown was requested before every passport was processed.
+    pub interrupted: bool,
+}
+
+/// Scans passports, scores them and pushes updates to the contract.
+///
+/// The oracle is kept alive across cycles in daemon mode, so everything it
+/// holds (HTTP clients, signer, parsed configuration) is built only once.
+/// What was already submitted is tracked in the [`AgentStore`], which also
+/// survives restarts.
+pub struct PassportOracle {
+    config: AppConfig,
+    /// Parsed and validated once at startup.
+    rules: RuleSet,
+    client: ChainClient,
+    updater: Option<PassportUpdater>,
+    store: AgentStore,
+    index: ActivityIndex,
+    discovery: ChainDiscovery,
+    cross_chain_ids: Vec<ChainId>,
+}
+
+impl PassportOracle {
+    pub fn new(
+        config: AppConfig,
+        rules: RuleSet,
+        client: ChainClient,
+        updater: Option<PassportUpdater>,
+        store: AgentStore,
+        discovery: ChainDiscovery,
+    ) -> Self {
+        let cross_chain_ids = config
+            .cross_chain_ids
+            .iter()
+            .filter_map(|chain_str| match ChainId::from_str(chain_str) {
+                Ok(chain_id) => Some(chain_id),
+                Err(err) => {
+                    tracing::warn!(
+                        chain = %chain_str,
+                        error = ?err,
+                        "Failed to parse cross-chain ID, skipping"
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        let index = ActivityIndex::new(client.clone(), store.clone());
+        Self {
+            config,
+            rules,
+            client,
+            updater,
+            store,
+            index,
+            discovery,
+            cross_chain_ids,
+        }
+    }
+
+    /// Re-runs the scan loop every `poll_interval_secs` until `shutdown` fires.
+    ///
+    /// A failed cycle (for example an unreachable GraphQL endpoint) is logged and
+    /// retried on the next tick instead of terminating the daemon.
+    pub async fn run_daemon(&self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        let period = Duration::from_secs(self.config.poll_interval_secs.max(1));
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            poll_interval_secs = period.as_secs(),
+            "Passport oracle daemon started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+            if *shutdown.borrow() {
+                break;
+            }
+
+            match self.run_cycle(&shutdown).await {
+                Ok(report) => tracing::info!(
+                    scanned = report.scanned,
+                    submitted = report.submitted,
+                    proposed = report.proposed,
+                    up_to_date = report.up_to_date,
+                    failed = report.failed,
+                    interrupted = report.interrupted,
+                    "Scan cycle finished"
+                ),
+                Err(err) => tracing::error!(error = %err, "Scan cycle failed"),
+            }
+
+            if *shutdown.borrow() {
+                break;
+            }
+        }
+
+        tracing::info!("Passport oracle daemon stopped");
+        Ok(())
+    }
+
+    /// Indexes new chain activity, then scores every passport once.
+    ///
+    /// Errors for individual chains or passports are logged and counted; only a
+    /// failure to list passports aborts the cycle. The shutdown flag is checked
+    /// before each passport is started, so in-flight `submit_update` calls always
+    /// run to completion.
+    pub async fn run_cycle(&self, shutdown: &watch::Receiver<bool>) -> Result<CycleReport> {
+        let passports = self
+            .client
+            .all_passports()
+            .await
+            .context("failed to fetch passports")?;
+        tracing::info!(count = passports.len(), "Fetched passports");
+        let total_supply = self
+            .client
+            .total_supply()
+            .await
+            .context("failed to fetch total supply")?;
+
+        // Sweep every chain that may hold passport activity, so the owner to chains
+        // table learns about signers on chains nobody listed explicitly.
+        let mut chains: BTreeSet<ChainId> = self.cross_chain_ids.iter().copied().collect();
+        chains.extend(self.discovery.candidate_chains()?);
+        chains.extend(
+            passports
+                .iter()
+                .filter_map(|passport| passport.owner_chain_id().ok()),
+        );
+        self.sync_chains(&chains).await;
+
+        // Passports are scored concurrently; `buffered` keeps results in listing order.
+        let concurrency = self.client.max_concurrent_requests();
+        let outcomes = stream::iter(passports)
+            .map(|passport| async move {
+                if *shutdown.borrow() {
+                    return None;
+                }
+                let owner = passport.owner.clone();
+                Some((owner, self.process_passport(passport, total_supply).await))
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut report = CycleReport::default();
+        for outcome in outcomes {
+            let Some((owner, outcome)) = outcome else {
+                report.interrupted = true;
+                continue;
+            };
+            report.scanned += 1;
+            match outcome {
+                Ok(PassportOutcome::Submitted) => report.submitted += 1,
+                Ok(PassportOutcome::Proposed) => report.proposed += 1,
+                Ok(PassportOutcome::UpToDate) | Ok(PassportOutcome::DryRun) => {
+                    report.up_to_date += 1
+                }
+                Err(err) => {
+                    report.failed += 1;
+                    tracing::warn!(owner = %owner, error = %err, "Failed to process passport");
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Brings the activity index up to date for `chains`; failures only skip that chain.
+    async fn sync_chains(&self, chains: &BTreeSet<ChainId>) {
+        let results = stream::iter(chains)
+            .map(|chain_id| async move { (chain_id, self.index.sync_chain(chain_id).await) })
+            .buffered(self.client.max_concurrent_requests())
+            .collect::<Vec<_>>()
+            .await;
+
+        for (chain_id, result) in results {
+            match result {
+                Ok(sync) => tracing::debug!(
+                    chain_id = %chain_id,
+                    new_operations = sync.new_operations,
+                    complete = sync.complete,
+                    "Chain synced"
+                ),
+                Err(err) => tracing::warn!(
+                    chain_id = %chain_id,
+                    error = %err,
+                    "Failed to index chain, scoring with previously indexed activity"
+                ),
+            }
+        }
+    }
+
+    async fn process_passport(
+        &self,
+        passport: PassportInfo,
+        total_supply: u64,
+    ) -> Result<PassportOutcome> {
+        let owner = passport
+            .owner_account()
+            .context("failed to parse owner")?;
+        let owner_chain = passport
+            .owner_chain_id()
+            .context("failed to parse owner chain id")?;
+        let token_id_bytes = passport
+            .token_id_bytes()
+            .context("failed to parse token ID")?
+            .ok_or_else(|| anyhow!("token ID is empty"))?;
+
+        // CROSS-CHAIN FEATURE: Aggregate activity over every chain discovered for this owner
+        let chains_to_query = self.discovery.owner_chains(&owner, owner_chain)?;
+        tracing::debug!(
+            owner = %owner,
+            chain_count = chains_to_query.len(),
+            "Owner chains discovered"
+        );
+
+        let (activity, complete) = self.index.owner_aggregates(&owner, &chains_to_query)?;
+        if !complete {
+            tracing::warn!(
+                owner = %owner,
+                "Activity history is incomplete, score may be understated"
+            );
+        }
+
+        let passport_id = hex::encode(&token_id_bytes);
+        let mut record = self.store.passport(&passport_id)?;
+        record.sync_epoch(PassportEpoch {
+            owner,
+            created_at: passport.created_at.unwrap_or(0),
+            transfers: passport.transfers.as_ref().map_or(0, |t| t.len() as u64),
+        });
+        let chain_achievements = passport.achievement_codes();
+        let chain_score = passport.effective_score();
+
+        let chain_revision = passport.revision.unwrap_or(0);
+        // Updates run on the oracle's own chain, where it is registered, and are
+        // forwarded by the contract to the chain the passport is anchored on.
+        let operation_chain = ChainId::from_str(&self.config.operation_chain_id)
+            .context("failed to parse operation_chain_id")?;
+
+        // A pending update is settled against what the chain holds: confirmed if its
+        // codes and score landed, dropped and recomputed otherwise. Without a signer
+        // (dry run) there is nothing to compare issuers with, so it is left alone.
+        if record.pending.is_some() {
+            if let Some(updater) = &self.updater {
+                let oracle = updater
+                    .signer_owner(operation_chain)
+                    .context("failed to resolve the oracle account")?;
+                let chain = ChainView {
+                    revision: chain_revision,
+                    score: chain_score,
+                    issued_codes: passport.codes_issued_by(&oracle),
+                };
+                record.reconcile_pending(&chain, store::unix_now());
+            }
+        }
+
+        // The GraphQL read is eventually consistent; behind our own last update it
+        // would yield a stale expected revision, so wait for it to catch up.
+        if chain_revision < record.submitted_revision() {
+            tracing::debug!(
+                passport_id = %passport_id,
+                chain_revision = chain_revision,
+                submitted_revision = record.submitted_revision(),
+                "Passport read lags our last update, skipping until it catches up"
+            );
+            self.store.save_passport(&passport_id, &record).await?;
+            return Ok(PassportOutcome::UpToDate);
+        }
+
+        // A disputed update blocks further updates until the admin resolves it.
+        if passport
+            .pending_update
+            .as_ref()
+            .is_some_and(|pending| pending.dispute.is_some())
+        {
+            tracing::info!(
+                passport_id = %passport_id,
+                "Passport update is disputed, skipping until the admin resolves it"
+            );
+            self.store.save_passport(&passport_id, &record).await?;
+            return Ok(PassportOutcome::UpToDate);
+        }
+
+        let existing_achievements = record.known_achievements(chain_revision, &chain_achievements);
+        let existing_score = record.known_score(chain_revision, chain_score);
+        record.cursors = self.index.checkpoints(&chains_to_query)?;
+
+        let context = ObservationContext::from_aggregates(passport, &activity)
+            .with_total_supply(total_supply);
+        let result = scoring::evaluate_rules_with_llm(&self.config, &self.rules, &context).await;
+
+        // Calculate only NEW achievements, by code
+        let evidence_hash = context.evidence_hash();
+        let new_achievements: Vec<AchievementInput> = result
+            .achievements
+            .iter()
+            .filter(|a| !existing_achievements.contains(&a.code))
+            .map(|a| AchievementInput {
+                code: a.code.clone(),
+                points: a.points.unwrap_or(0),
+                evidence_hash: Some(evidence_hash.clone()),
+            })
+            .collect();
+        let score_delta = result.score.saturating_sub(existing_score);
+
+        tracing::info!(
+            passport_id = %context.passport_id,
+            total_score = result.score,
+            existing_score = existing_score,
+            score_delta = score_delta,
+            new_achievement_count = new_achievements.len(),
+            "Passport evaluated"
+        );
+
+        if new_achievements.is_empty() && score_delta == 0 {
+            tracing::info!(
+                passport_id = %context.passport_id,
+                "No updates needed - passport is up to date"
+            );
+            self.store.save_passport(&passport_id, &record).await?;
+            return Ok(PassportOutcome::UpToDate);
+        }
+
+        let Some(updater) = &self.updater else {
+            tracing::info!("Dry run mode - skipping blockchain submission");
+            return Ok(PassportOutcome::DryRun);
+        };
+
+        // Record the submission before sending it, so a crash in between is not
+        // mistaken for "never sent" on the next start.
+        record.pending = Some(PendingSubmission {
+            target_score: existing_score + score_delta,
+            score_increase: score_delta,
+            achievements: new_achievements.iter().map(|a| a.code.clone()).collect(),
+            base_revision: chain_revision,
+            created_at: store::unix_now(),
+            wait_until: None,
+        });
+        self.store.save_passport(&passport_id, &record).await?;
+
+        let wallet_path = PathBuf::from(&self.config.wallet_path);
+        let mut attempt = 1;
+        loop {
+            // Every attempt carries the same expected revision, so at most one applies.
+            let update_args = UpdateArgs {
+                token_id: TokenId {
+                    id: token_id_bytes.clone(),
+                },
+                target_chain: Some(owner_chain),
+                expected_revision: chain_revision,
+                new_achievements: new_achievements.clone(),
+                score_increase: score_delta,
+            };
+            match updater
+                .submit_update(operation_chain, update_args, &wallet_path)
+                .await
+            {
+                Ok(()) => break,
+                Err(err)
+                    if attempt < SUBMIT_ATTEMPTS
+                        && !is_stale_update(&err)
+                        && !is_under_review(&err) =>
+                {
+                    tracing::warn!(
+                        passport_id = %passport_id,
+                        attempt = attempt,
+                        error = %err,
+                        "Update submission failed, retrying"
+                    );
+                    tokio::time::sleep(SUBMIT_RETRY_DELAY * attempt).await;
+                    attempt += 1;
+                }
+                // The pending record stays; the next cycle settles it against the chain.
+                Err(err) => return Err(err.context("failed to submit update")),
+            }
+        }
+
+        // Accepted by the node, but a forwarded update can still be rejected on the
+        // passport chain; the next cycles confirm it once the chain shows it. Under
+        // a quorum the vote may only have been recorded: then it is kept until its
+        // proposal expires, and recomputed afterwards if the quorum never formed.
+        let proposal_expiry = self
+            .open_proposal_expiry(&token_id_bytes, chain_revision, operation_chain)
+            .await;
+        let outcome = match proposal_expiry {
+            Some(_) => PassportOutcome::Proposed,
+            None => PassportOutcome::Submitted,
+        };
+        if let Some(pending) = &mut record.pending {
+            pending.wait_until =
+                Some(proposal_expiry.unwrap_or_else(|| store::unix_now() + SETTLE_WINDOW_SECS));
+        }
+        self.store.save_passport(&passport_id, &record).await?;
+
+        match outcome {
+            PassportOutcome::Proposed => tracing::info!(
+                passport_id = %context.passport_id,
+                "Quorum vote recorded, waiting for other oracles"
+            ),
+            _ => tracing::info!(
+                passport_id = %context.passport_id,
+                "Update submitted to blockchain"
+            ),
+        }
+        Ok(outcome)
+    }
+
+    /// Expiry, in Unix seconds, of the open proposal at `revision` that this oracle
+    /// voted for, if its vote did not complete a quorum. Lookup failures count as
+    /// "no proposal", which only shortens how long the update is waited for.
+    async fn open_proposal_expiry(
+        &self,
+        token_id: &[u8],
+        revision: u64,
+        operation_chain: ChainId,
+    ) -> Option<u64> {
+        let oracle = self.updater.as_ref()?.signer_owner(operation_chain).ok()?;
+        let proposals = match self.client.pending_proposals(token_id).await {
+            Ok(proposals) => proposals,
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to read quorum proposals");
+                return None;
+            }
+        };
+        proposals
+            .iter()
+            .find(|proposal| proposal.revision == revision && proposal.approved_by(&oracle))
+            .map(|proposal| proposal.expires_at.micros() / 1_000_000)
+    }
+}
+
+/// Whether the contract rejected the update because the passport moved on;
+/// retrying the same update can never succeed then.
+fn is_stale_update(err: &anyhow::Error) -> bool {
+    format!("{err:#}").contains(&format!("[{}]", PassportError::StaleUpdate.code()))
+}
+
+/// Whether the passport still has an oracle update inside its challenge window;
+/// retrying cannot succeed before the window ends.
+fn is_under_review(err: &anyhow::Error) -> bool {
+    format!("{err:#}").contains(&format!("[{}]", PassportError::UnderReview.code()))
+}
+
+/// Returns a receiver that flips to `true` on SIGINT or SIGTERM.
+pub fn shutdown_signal() -> watch::Receiver<bool> {
+    let (sender, receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        tracing::info!("Shutdown requested, finishing in-flight updates");
+        let _ = sender.send(true);
+    });
+    receiver
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to install SIGTERM handler, using Ctrl-C only");
+            let _ = tokio::signal::ctrl_c().await;
+            return;
+        }
+    };
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate.recv() => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,594 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use linera_base::identifiers::ApplicationId;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+use crate::scoring::ObservationContext;
+
+/// Aggregates computed by `ObservationContext` that conditions may compare against.
+///
+/// `total_transactions` counts every indexed operation once; `total_actions` is
+/// kept as an alias of it. `distinct_apps` counts applications the owner ran
+/// operations on, `transferred_tokens` the whole tokens sent in transfers, and
+/// `total_supply` the passports minted when the rules are evaluated.
+pub const AGGREGATE_KEYS: &[&str] = &[
+    "total_transactions",
+    "total_actions",
+    "unique_active_days",
+    "wallet_age_days",
+    "current_streak_days",
+    "longest_streak_days",
+    "distinct_apps",
+    "transferred_tokens",
+    "total_supply",
+];
+
+/// Action types tracked per event; each may also be suffixed with `:<id>`.
+pub const ACTION_KEYS: &[&str] = &["system_transfer", "user_operation", "create_application"];
+
+/// Achievement rules file, validated against the schema and parsed once at load time.
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub scoring: ScoringRules,
+    pub achievements: Vec<AchievementRule>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoringRules {
+    pub transactions_per_point: u64,
+    pub daily_activity_points: u64,
+    pub wallet_age_points_per_day: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AchievementRule {
+    pub code: String,
+    pub explanation: String,
+    pub points: Option<u64>,
+    /// `None` means the achievement is always awarded.
+    pub condition: Option<Condition>,
+}
+
+/// Condition grammar of `config/achievements.json`.
+///
+/// An object with several keys is the conjunction of its entries. Besides the
+/// `all`/`any`/`not` combinators, every key names a metric and maps to a threshold:
+///
+/// ```json
+/// {"any": [
+///     {"total_transactions": {"min": 100}},
+///     {"all": [{"user_operation:<app_id>": {"min": 5}}, {"not": {"wallet_age_days": {"max": 7}}}]}
+/// ]}
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare { metric: Metric, threshold: Threshold },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Metric {
+    /// One of [`AGGREGATE_KEYS`].
+    Aggregate(String),
+    /// An action type from [`ACTION_KEYS`], optionally `<type>:<id>`; compares its count.
+    Action(String),
+    /// `transactions_last_<N>d` or `active_days_last_<N>d`: a total over the last `days` days.
+    Window { kind: WindowKind, days: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowKind {
+    Transactions,
+    ActiveDays,
+}
+
+impl WindowKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            WindowKind::Transactions => "transactions_last_",
+            WindowKind::ActiveDays => "active_days_last_",
+        }
+    }
+}
+
+/// Inclusive bounds; every bound that is set must hold.
+///
+/// This is the only threshold vocabulary: `{"min": N}`, `{"max": N}`, `{"eq": N}` or a
+/// combination, for aggregates and action counts alike.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Threshold {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub eq: Option<u64>,
+}
+
+impl RuleSet {
+    /// Reads, validates and parses a rules file. Any unknown key is an error.
+    pub async fn load(path: &str) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read rules file {path}"))?;
+        let json: Value = serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse rules file {path}"))?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &Value) -> Result<Self> {
+        validate_rules(json)?;
+
+        let scoring_rules = json.get("scoring_rules");
+        let scoring_value = |key: &str, default: u64| {
+            scoring_rules
+                .and_then(|rules| rules.get(key))
+                .and_then(|value| value.as_u64())
+                .unwrap_or(default)
+        };
+        let scoring = ScoringRules {
+            transactions_per_point: scoring_value("transactions_per_point", 10),
+            daily_activity_points: scoring_value("daily_activity_points", 10),
+            wallet_age_points_per_day: scoring_value("wallet_age_points_per_day", 1),
+        };
+
+        let mut achievements = Vec::new();
+        for entry in json
+            .get("achievements")
+            .and_then(|value| value.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let code = entry
+                .get("code")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let condition = entry
+                .get("condition")
+                .map(|condition| Condition::parse(condition, &code))
+                .transpose()?;
+            achievements.push(AchievementRule {
+                explanation: entry
+                    .get("explanation")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                points: entry.get("points").and_then(|v| v.as_u64()),
+                condition,
+                code,
+            });
+        }
+
+        Ok(Self {
+            scoring,
+            achievements,
+        })
+    }
+
+    /// Problems the schema cannot see: duplicate codes and thresholds that
+    /// never match, always match, or carry bounds that have no effect.
+    pub fn lint(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        let mut codes = HashSet::new();
+        for rule in &self.achievements {
+            if !codes.insert(rule.code.as_str()) {
+                issues.push(format!("{}: duplicate achievement code", rule.code));
+            }
+            if let Some(condition) = &rule.condition {
+                condition.lint(&rule.code, &mut issues);
+            }
+        }
+        issues
+    }
+}
+
+impl Condition {
+    /// Parses a condition object; `path` locates it in error messages.
+    pub fn parse(value: &Value, path: &str) -> Result<Self> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| anyhow!("{path}: condition must be an object"))?;
+
+        let mut parts = Vec::with_capacity(map.len());
+        for (key, inner) in map {
+            let here = format!("{path}.{key}");
+            let part = match key.as_str() {
+                "all" => Condition::All(Self::parse_list(inner, &here)?),
+                "any" => Condition::Any(Self::parse_list(inner, &here)?),
+                "not" => Condition::Not(Box::new(Self::parse(inner, &here)?)),
+                _ => Condition::Compare {
+                    metric: Metric::parse(key).with_context(|| format!("{here}: unknown key"))?,
+                    threshold: Threshold::parse(inner, &here)?,
+                },
+            };
+            parts.push(part);
+        }
+
+        Ok(match parts.pop() {
+            Some(single) if parts.is_empty() => single,
+            Some(last) => {
+                parts.push(last);
+                Condition::All(parts)
+            }
+            None => Condition::All(Vec::new()),
+        })
+    }
+
+    fn parse_list(value: &Value, path: &str) -> Result<Vec<Self>> {
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow!("{path}: expected an array of conditions"))?;
+        ensure!(!items.is_empty(), "{path}: needs at least one condition");
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| Self::parse(item, &format!("{path}[{index}]")))
+            .collect()
+    }
+
+    pub fn matches(&self, context: &ObservationContext) -> bool {
+        match self {
+            Condition::All(parts) => parts.iter().all(|part| part.matches(context)),
+            Condition::Any(parts) => parts.iter().any(|part| part.matches(context)),
+            Condition::Not(inner) => !inner.matches(context),
+            // A metric the context does not carry (e.g. `total_supply` when the
+            // supply is unknown) never matches, rather than counting as zero.
+            Condition::Compare { metric, threshold } => metric
+                .value(context)
+                .is_some_and(|value| threshold.contains(value)),
+        }
+    }
+
+    fn lint(&self, path: &str, issues: &mut Vec<String>) {
+        match self {
+            Condition::All(parts) | Condition::Any(parts) => {
+                for (index, part) in parts.iter().enumerate() {
+                    part.lint(&format!("{path}[{index}]"), issues);
+                }
+            }
+            Condition::Not(inner) => inner.lint(&format!("{path}.not"), issues),
+            Condition::Compare { metric, threshold } => {
+                let here = format!("{path}.{}", metric.key());
+                if let Metric::Window {
+                    kind: WindowKind::ActiveDays,
+                    days,
+                } = metric
+                {
+                    if threshold.min.is_some_and(|min| min > *days) {
+                        issues.push(format!("{here}: min is longer than the window, never matches"));
+                    }
+                }
+                threshold.lint(&here, issues)
+            }
+        }
+    }
+}
+
+impl Metric {
+    pub fn parse(key: &str) -> Result<Self> {
+        if AGGREGATE_KEYS.contains(&key) {
+            return Ok(Metric::Aggregate(key.to_string()));
+        }
+        if ACTION_KEYS.contains(&key) {
+            return Ok(Metric::Action(key.to_string()));
+        }
+        for kind in [WindowKind::Transactions, WindowKind::ActiveDays] {
+            if let Some(days) = key
+                .strip_prefix(kind.prefix())
+                .and_then(|rest| rest.strip_suffix('d'))
+            {
+                let days = days
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| anyhow!("`{key}`: window must be a positive number of days"))?;
+                return Ok(Metric::Window { kind, days });
+            }
+        }
+        match key.split_once(':') {
+            Some(("user_operation", app_id)) => {
+                ApplicationId::from_str(app_id)
+                    .map_err(|err| anyhow!("invalid application id {app_id}: {err}"))?;
+                Ok(Metric::Action(key.to_string()))
+            }
+            Some(("create_application", module_id)) if !module_id.is_empty() => {
+                Ok(Metric::Action(key.to_string()))
+            }
+            _ => bail!("`{key}` is not a known aggregate or action"),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        match self {
+            Metric::Aggregate(key) | Metric::Action(key) => key.clone(),
+            Metric::Window { kind, days } => format!("{}{days}d", kind.prefix()),
+        }
+    }
+
+    fn value(&self, context: &ObservationContext) -> Option<u64> {
+        match self {
+            Metric::Aggregate(key) => context.aggregates.get(key).and_then(|v| v.as_u64()),
+            Metric::Action(key) => Some(
+                context
+                    .actions
+                    .iter()
+                    .find(|action| action.action_type == *key)
+                    .map_or(0, |action| action.count),
+            ),
+            Metric::Window {
+                kind: WindowKind::Transactions,
+                days,
+            } => Some(context.transactions_in_window(*days)),
+            Metric::Window {
+                kind: WindowKind::ActiveDays,
+                days,
+            } => Some(context.active_days_in_window(*days)),
+        }
+    }
+}
+
+impl Threshold {
+    fn parse(value: &Value, path: &str) -> Result<Self> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| anyhow!("{path}: threshold must be an object"))?;
+        let mut threshold = Threshold::default();
+        for (key, bound) in map {
+            let bound = bound
+                .as_u64()
+                .ok_or_else(|| anyhow!("{path}.{key}: expected a non-negative integer"))?;
+            match key.as_str() {
+                "min" => threshold.min = Some(bound),
+                "max" => threshold.max = Some(bound),
+                "eq" => threshold.eq = Some(bound),
+                other => bail!("{path}: unknown threshold operator `{other}`"),
+            }
+        }
+        Ok(threshold)
+    }
+
+    /// Constraints that parse but cannot do what their author meant.
+    fn lint(&self, path: &str, issues: &mut Vec<String>) {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                issues.push(format!("{path}: min {min} is above max {max}, never matches"));
+            }
+        }
+        if let Some(eq) = self.eq {
+            if self.min.is_some_and(|min| eq < min) || self.max.is_some_and(|max| eq > max) {
+                issues.push(format!("{path}: eq {eq} is outside min/max, never matches"));
+            } else if self.min.is_some() || self.max.is_some() {
+                issues.push(format!("{path}: min/max are ignored next to eq {eq}"));
+            }
+        }
+        if self.min == Some(0) && self.max.is_none() && self.eq.is_none() {
+            issues.push(format!("{path}: min 0 always matches"));
+        }
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.min.map_or(true, |min| value >= min)
+            && self.max.map_or(true, |max| value <= max)
+            && self.eq.map_or(true, |eq| value == eq)
+    }
+}
+
+pub fn validate_rules(rules: &Value) -> Result<()> {
+    static SCHEMA: Lazy<jsonschema::JSONSchema> = Lazy::new(|| {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["achievements"],
+            "additionalProperties": false,
+            "definitions": {
+                "threshold": {
+                    "type": "object",
+                    "properties": {
+                        "min": {"type": "integer", "minimum": 0},
+                        "max": {"type": "integer", "minimum": 0},
+                        "eq": {"type": "integer", "minimum": 0},
+                    },
+                    "minProperties": 1,
+                    "additionalProperties": false,
+                },
+                "condition": {
+                    "type": "object",
+                    "properties": {
+                        "all": {
+                            "type": "array",
+                            "minItems": 1,
+                            "items": {"$ref": "#/definitions/condition"},
+                        },
+                        "any": {
+                            "type": "array",
+                            "minItems": 1,
+                            "items": {"$ref": "#/definitions/condition"},
+                        },
+                        "not": {"$ref": "#/definitions/condition"},
+                    },
+                    "patternProperties": {
+                        "^(total_transactions|total_actions|unique_active_days|wallet_age_days|current_streak_days|longest_streak_days|distinct_apps|transferred_tokens|total_supply)$":
+                            {"$ref": "#/definitions/threshold"},
+                        "^(transactions|active_days)_last_[1-9][0-9]*d$":
+                            {"$ref": "#/definitions/threshold"},
+                        "^(system_transfer|user_operation|create_application)(:.+)?$":
+                            {"$ref": "#/definitions/threshold"},
+                    },
+                    "additionalProperties": false,
+                },
+            },
+            "properties": {
+                "scoring_rules": {
+                    "type": "object",
+                    "properties": {
+                        "transactions_per_point": {"type": "integer", "minimum": 1},
+                        "daily_activity_points": {"type": "integer", "minimum": 0},
+                        "wallet_age_points_per_day": {"type": "integer", "minimum": 0},
+                    },
+                    "additionalProperties": false,
+                },
+                "achievements": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["code", "explanation"],
+                        "properties": {
+                            "code": {"type": "string"},
+                            "explanation": {"type": "string"},
+                            "points": {"type": "integer", "minimum": 0},
+                            "condition": {"$ref": "#/definitions/condition"},
+                        },
+                        "additionalProperties": false,
+                    },
+                },
+            },
+        });
+        jsonschema::JSONSchema::compile(&schema).expect("static rules schema should compile")
+    });
+
+    if let Err(errors) = SCHEMA.validate(rules) {
+        let messages = errors
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!("rules validation error: {messages}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn compare(key: &str, min: u64) -> Condition {
+        Condition::Compare {
+            metric: Metric::parse(key).unwrap(),
+            threshold: Threshold {
+                min: Some(min),
+                ..Threshold::default()
+            },
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        let unknown_metric = json!({"total_transaction": {"min": 1}});
+        assert!(Condition::parse(&unknown_metric, "rule").is_err());
+
+        let unknown_operator = json!({"total_transactions": {"gte": 1}});
+        assert!(Condition::parse(&unknown_operator, "rule").is_err());
+
+        let zero_window = json!({"transactions_last_0d": {"min": 1}});
+        assert!(Condition::parse(&zero_window, "rule").is_err());
+
+        let bad_app = json!({"user_operation:not-an-app": {"min": 1}});
+        assert!(Condition::parse(&bad_app, "rule").is_err());
+    }
+
+    #[test]
+    fn parse_combinators() {
+        let value = json!({"any": [
+            {"total_transactions": {"min": 100}},
+            {"all": [
+                {"user_operation": {"min": 5}},
+                {"not": {"wallet_age_days": {"min": 7}}},
+            ]},
+        ]});
+        let expected = Condition::Any(vec![
+            compare("total_transactions", 100),
+            Condition::All(vec![
+                compare("user_operation", 5),
+                Condition::Not(Box::new(compare("wallet_age_days", 7))),
+            ]),
+        ]);
+        assert_eq!(Condition::parse(&value, "rule").unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_object_is_conjunction() {
+        let value = json!({
+            "total_transactions": {"min": 10},
+            "unique_active_days": {"min": 3},
+        });
+        match Condition::parse(&value, "rule").unwrap() {
+            Condition::All(parts) => {
+                assert_eq!(parts.len(), 2);
+                assert!(parts.contains(&compare("total_transactions", 10)));
+                assert!(parts.contains(&compare("unique_active_days", 3)));
+            }
+            other => panic!("expected a conjunction, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_empty_lists() {
+        assert!(Condition::parse(&json!({"any": []}), "rule").is_err());
+        assert!(Condition::parse(&json!({"all": {}}), "rule").is_err());
+    }
+
+    #[test]
+    fn threshold_bounds_are_inclusive() {
+        let range = Threshold {
+            min: Some(10),
+            max: Some(20),
+            eq: None,
+        };
+        assert!(!range.contains(9));
+        assert!(range.contains(10));
+        assert!(range.contains(20));
+        assert!(!range.contains(21));
+
+        let exact = Threshold {
+            eq: Some(5),
+            ..Threshold::default()
+        };
+        assert!(exact.contains(5));
+        assert!(!exact.contains(4));
+        assert!(!exact.contains(6));
+
+        assert!(Threshold::default().contains(0));
+    }
+
+    #[test]
+    fn lint_reports_min_above_max() {
+        let rules = RuleSet::from_json(&json!({"achievements": [{
+            "code": "EMPTY_RANGE",
+            "explanation": "never awarded",
+            "condition": {"total_transactions": {"min": 50, "max": 10}},
+        }]}))
+        .unwrap();
+        let issues = rules.lint();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("min 50 is above max 10"), "{issues:?}");
+    }
+
+    #[test]
+    fn lint_accepts_sound_rules() {
+        let rules = RuleSet::from_json(&json!({"achievements": [{
+            "code": "ACTIVE",
+            "explanation": "ten operations",
+            "condition": {"total_transactions": {"min": 10, "max": 50}},
+        }]}))
+        .unwrap();
+        assert!(rules.lint().is_empty());
+    }
+
+    #[test]
+    fn missing_aggregate_never_matches() {
+        let context = ObservationContext {
+            passport_id: String::new(),
+            owner: String::new(),
+            actions: Vec::new(),
+            aggregates: Default::default(),
+            daily_counts: Default::default(),
+            today: 0,
+        };
+        let condition = Condition::parse(&json!({"total_supply": {"max": 999}}), "rule").unwrap();
+        assert!(!condition.matches(&context));
+        let negated = Condition::Not(Box::new(condition));
+        assert!(negated.matches(&context));
+    }
+}
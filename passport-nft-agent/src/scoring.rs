@@ -2,11 +2,11 @@ use linera_base::{
     data_types::{Amount, BlockHeight, Timestamp},
     identifiers::{AccountOwner, ApplicationId, ChainId},
 };
-use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::{chain_client::PassportInfo, config::AppConfig};
+use crate::{chain_client::PassportInfo, config::AppConfig, rules::RuleSet};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ActionEvent {
@@ -21,6 +21,12 @@ pub struct ObservationContext {
     pub owner: String,
     pub actions: Vec<ActionEvent>,
     pub aggregates: HashMap<String, serde_json::Value>,
+    /// Operations per day (days since the Unix epoch), for time-windowed conditions.
+    #[serde(default)]
+    pub daily_counts: BTreeMap<u64, u64>,
+    /// Day the context was built on, so windows are relative to evaluation time.
+    #[serde(default)]
+    pub today: u64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -36,6 +42,27 @@ pub struct AchievementResult {
     pub achievements: Vec<AchievementEntry>,
 }
 
+/// Running totals of one owner's activity, folded from indexed events.
+///
+/// These are persisted per owner and chain by the activity index, so each cycle
+/// only folds operations that are newer than the chain's checkpoint.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ActivityAggregates {
+    /// Count per action key (`system_transfer`, `user_operation:<app_id>`, ...)
+    pub actions: BTreeMap<String, ActionTally>,
+    /// Number of operations per day (days since the Unix epoch)
+    pub daily_counts: BTreeMap<u64, u64>,
+    pub first_seen: Option<Timestamp>,
+    /// Native tokens sent in system transfers
+    pub transferred: Amount,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ActionTally {
+    pub count: u64,
+    pub last_seen: Option<Timestamp>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct OwnerActivityEvent {
     pub owner: AccountOwner,
@@ -118,14 +145,44 @@ impl OwnerActivityEvent {
             kind: ActivityKind::CreateApplication { module_id },
         }
     }
+
+    /// Sets the timestamp of the block the operation was included in.
+    pub fn at(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
     }
+}
 
 impl ObservationContext {
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::to_value(self).expect("ObservationContext serializes")
     }
 
+    /// SHA3-256 (hex) of the context, recorded on-chain as the evidence for awarded achievements.
+    ///
+    /// Hashes the `serde_json::Value` form, whose maps are sorted, so the digest does not
+    /// depend on `HashMap` iteration order.
+    pub fn evidence_hash(&self) -> String {
+        let bytes = serde_json::to_vec(&self.to_json()).expect("ObservationContext serializes");
+        hex::encode(Sha3_256::digest(bytes))
+    }
+
     pub fn from_passport(passport: PassportInfo, activity: Vec<OwnerActivityEvent>) -> Self {
+        let mut aggregates = ActivityAggregates::default();
+        for event in &activity {
+            aggregates.fold(event);
+        }
+        Self::from_aggregates(passport, &aggregates)
+    }
+
+    /// Adds the number of minted passports, for conditions on `total_supply`.
+    pub fn with_total_supply(mut self, total_supply: u64) -> Self {
+        self.aggregates
+            .insert("total_supply".to_string(), serde_json::json!(total_supply));
+        self
+    }
+
+    pub fn from_aggregates(passport: PassportInfo, activity: &ActivityAggregates) -> Self {
         let passport_id = passport
             .token_id_bytes()
             .ok()
@@ -134,65 +191,129 @@ impl ObservationContext {
             .unwrap_or_else(|| "unknown".to_string());
         let owner = passport.owner.clone();
 
+        let now = Timestamp::from(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_micros() as u64);
+        let today = day_number(now);
+
         // Calculate wallet age from first transaction
         let wallet_age_days = activity
-            .iter()
-            .filter_map(|event| event.timestamp)
-            .min()
+            .first_seen
             .map(|first_ts| {
-                let now = Timestamp::from(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_micros() as u64);
                 let age_micros = now.micros().saturating_sub(first_ts.micros());
                 let age_secs = age_micros / 1_000_000;
                 age_secs / 86400 // convert to days
             })
             .unwrap_or(0);
 
-        let (actions, unique_days) = categorize_activity(&activity);
-        let aggregates = compute_aggregates(&actions, unique_days.len(), wallet_age_days);
+        let actions = activity
+            .actions
+            .iter()
+            .map(|(key, tally)| ActionEvent {
+                action_type: key.clone(),
+                count: tally.count,
+                last_seen: tally.last_seen.map(|ts| ts.to_string()),
+            })
+            .collect::<Vec<_>>();
+        let aggregates = compute_aggregates(
+            &actions,
+            &activity.daily_counts,
+            today,
+            wallet_age_days,
+            activity.transferred,
+        );
 
         ObservationContext {
             passport_id,
             owner,
             actions,
             aggregates,
+            daily_counts: activity.daily_counts.clone(),
+            today,
         }
     }
-}
 
-pub fn compute_base_score(total_transactions: u64, transactions_per_point: u64) -> u64 {
-    total_transactions / transactions_per_point
+    /// Operations in the last `days` days, today included.
+    pub fn transactions_in_window(&self, days: u64) -> u64 {
+        self.window(days).map(|(_, count)| count).sum()
+    }
+
+    /// Days with at least one operation in the last `days` days, today included.
+    pub fn active_days_in_window(&self, days: u64) -> u64 {
+        self.window(days).filter(|(_, count)| **count > 0).count() as u64
+    }
+
+    fn window(&self, days: u64) -> impl Iterator<Item = (&u64, &u64)> {
+        let start = (self.today + 1).saturating_sub(days);
+        self.daily_counts.range(start..=self.today)
+    }
 }
 
-pub async fn evaluate_rules(
-    config: &AppConfig,
-    context: &ObservationContext,
-) -> anyhow::Result<AchievementResult> {
-    let rules_content = tokio::fs::read_to_string(&config.rules_path).await?;
-    let rules_json: serde_json::Value = serde_json::from_str(&rules_content)?;
-    validate_rules(&rules_json)?;
+impl ActivityAggregates {
+    /// Adds one indexed event to the totals.
+    pub fn fold(&mut self, event: &OwnerActivityEvent) {
+        // Track unique days (convert timestamp to day number)
+        if let Some(ts) = event.timestamp {
+            *self.daily_counts.entry(day_number(ts)).or_insert(0) += 1;
+            self.first_seen = Some(self.first_seen.map_or(ts, |first| first.min(ts)));
+        }
 
-    // Get total transaction count
-    let total_transactions: u64 = context.actions.iter().map(|event| event.count).sum();
+        match &event.kind {
+            ActivityKind::SystemTransfer { amount, .. } => {
+                self.accumulate("system_transfer", event.timestamp);
+                self.transferred = self.transferred.saturating_add(*amount);
+            }
+            ActivityKind::UserOperation { application_id, .. } => {
+                let specific_key = format!("user_operation:{}", application_id);
+                self.accumulate(&specific_key, event.timestamp);
+                self.accumulate("user_operation", event.timestamp);
+            }
+            ActivityKind::CreateApplication { module_id } => {
+                self.accumulate("create_application", event.timestamp);
+                let specific_key = format!("create_application:{}", module_id);
+                self.accumulate(&specific_key, event.timestamp);
+            }
+        }
+    }
 
-    // Get scoring rules
-    let scoring_rules = rules_json.get("scoring_rules");
-    let transactions_per_point = scoring_rules
-        .and_then(|r| r.get("transactions_per_point"))
-        .and_then(|v| v.as_u64())
-        .unwrap_or(10);
+    /// Combines totals collected separately, e.g. on different chains.
+    pub fn merge(&mut self, other: &ActivityAggregates) {
+        for (key, tally) in &other.actions {
+            let entry = self.actions.entry(key.clone()).or_default();
+            entry.count += tally.count;
+            entry.last_seen = entry.last_seen.max(tally.last_seen);
+        }
+        for (day, count) in &other.daily_counts {
+            *self.daily_counts.entry(*day).or_insert(0) += count;
+        }
+        self.first_seen = match (self.first_seen, other.first_seen) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.transferred = self.transferred.saturating_add(other.transferred);
+    }
 
-    let daily_activity_points = scoring_rules
-        .and_then(|r| r.get("daily_activity_points"))
-        .and_then(|v| v.as_u64())
-        .unwrap_or(10);
+    fn accumulate(&mut self, key: &str, timestamp: Option<Timestamp>) {
+        let entry = self.actions.entry(key.to_string()).or_default();
+        entry.count += 1;
+        entry.last_seen = entry.last_seen.max(timestamp);
+    }
+}
 
-    let wallet_age_points_per_day = scoring_rules
-        .and_then(|r| r.get("wallet_age_points_per_day"))
+pub fn compute_base_score(total_transactions: u64, transactions_per_point: u64) -> u64 {
+    total_transactions / transactions_per_point
+}
+
+/// Scores `context` against an already loaded rule set.
+pub fn apply_rules(rules: &RuleSet, context: &ObservationContext) -> AchievementResult {
+    // Get total transaction count
+    let total_transactions = context
+        .aggregates
+        .get("total_transactions")
         .and_then(|v| v.as_u64())
-        .unwrap_or(1);
+        .unwrap_or(0);
 
     // Calculate base score from transactions
-    let base_score = compute_base_score(total_transactions, transactions_per_point);
+    let base_score =
+        compute_base_score(total_transactions, rules.scoring.transactions_per_point);
 
     // Get unique active days and wallet age from aggregates
     let unique_active_days = context
@@ -208,11 +329,24 @@ pub async fn evaluate_rules(
         .unwrap_or(0);
 
     // Calculate daily activity bonus and wallet age bonus
-    let daily_bonus = unique_active_days * daily_activity_points;
-    let age_bonus = wallet_age_days * wallet_age_points_per_day;
+    let daily_bonus = unique_active_days * rules.scoring.daily_activity_points;
+    let age_bonus = wallet_age_days * rules.scoring.wallet_age_points_per_day;
 
     // Apply achievement rules
-    let achievements = apply_rules(&rules_json, context, total_transactions);
+    let achievements = rules
+        .achievements
+        .iter()
+        .filter(|rule| {
+            rule.condition
+                .as_ref()
+                .map_or(true, |condition| condition.matches(context))
+        })
+        .map(|rule| AchievementEntry {
+            code: rule.code.clone(),
+            points: rule.points,
+            explanation: rule.explanation.clone(),
+        })
+        .collect::<Vec<_>>();
 
     // Sum up achievement points
     let achievement_points: u64 = achievements
@@ -234,232 +368,72 @@ pub async fn evaluate_rules(
         "Score calculation breakdown"
     );
 
-    Ok(AchievementResult {
+    AchievementResult {
         score: total_score,
         achievements,
-    })
-}
-
-fn validate_rules(rules: &serde_json::Value) -> anyhow::Result<()> {
-    static SCHEMA: Lazy<jsonschema::JSONSchema> = Lazy::new(|| {
-        let schema = serde_json::json!({
-            "type": "object",
-            "required": ["achievements"],
-            "properties": {
-                "scoring_rules": {
-                    "type": "object",
-                    "properties": {
-                        "transactions_per_point": {"type": "integer"},
-                        "base_multiplier": {"type": "integer"}
-                    }
-                },
-                "achievements": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "required": ["code", "explanation"],
-                        "properties": {
-                            "code": {"type": "string"},
-                            "explanation": {"type": "string"},
-                            "points": {"type": "integer", "minimum": 0},
-                            "condition": {"type": "object"},
-                        },
-                    },
-                },
-            },
-        });
-        jsonschema::JSONSchema::compile(&schema).expect("static rules schema should compile")
-    });
-
-    if let Err(errors) = SCHEMA.validate(rules) {
-        let messages = errors
-            .map(|err| err.to_string())
-            .collect::<Vec<_>>()
-            .join(", ");
-        anyhow::bail!("rules validation error: {messages}");
     }
-    Ok(())
 }
 
-fn apply_rules(
-    rules: &serde_json::Value,
-    context: &ObservationContext,
-    total_transactions: u64,
-) -> Vec<AchievementEntry> {
-    let mut results = Vec::new();
-    let Some(list) = rules.get("achievements").and_then(|value| value.as_array()) else {
-        return results;
-    };
-
-    for entry in list {
-        let code = entry
-            .get("code")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default();
-        let explanation = entry
-            .get("explanation")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default();
-        let points = entry.get("points").and_then(|v| v.as_u64());
-
-        let condition = entry.get("condition");
-        if condition_matches(condition, context, total_transactions) {
-            results.push(AchievementEntry {
-                code: code.to_string(),
-                points,
-                explanation: explanation.to_string(),
-            });
-        }
-    }
-
-    results
+/// Days since the Unix epoch (UTC).
+fn day_number(timestamp: Timestamp) -> u64 {
+    timestamp.micros() / 1_000_000 / 86400
 }
 
-fn condition_matches(
-    condition: Option<&serde_json::Value>,
-    context: &ObservationContext,
-    total_transactions: u64,
-) -> bool {
-    match condition {
-        None => true,
-        Some(serde_json::Value::Object(map)) => {
-            for (condition_key, constraint) in map {
-                match condition_key.as_str() {
-                    // Check for specific action types
-                    "system_transfer" | "user_operation" => {
-                        let Some(event) = context
-                            .actions
-                            .iter()
-                            .find(|action| action.action_type == *condition_key)
-                        else {
-                            return false;
-                        };
-
-                        if let Some(min_count) = constraint.get("min_count").and_then(|v| v.as_u64()) {
-                            if event.count < min_count {
-                                return false;
-                            }
-                        }
-                    }
-                    // Check total transactions milestone
-                    "total_transactions" => {
-                        if let Some(min_count) = constraint.get("min_count").and_then(|v| v.as_u64()) {
-                            if total_transactions < min_count {
-                                return false;
-                            }
-                        }
-                    }
-                    // Check for app creation (TODO: need to detect this from activity)
-                    // SECURITY FIX: Check for actual app creation (not just any UserOperation)
-                    "app_creation" => {
-                        let has_created_app = context
-                            .actions
-                            .iter()
-                            .any(|action| action.action_type == "create_application");
-
-                        if !has_created_app {
-                            return false;
-                        }
-                    }
-                    "unique_active_days" => {
-                        let unique_days = context
-                            .aggregates
-                            .get("unique_active_days")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
-
-                        if let Some(min_value) = constraint.get("min").and_then(|v| v.as_u64()) {
-                            if unique_days < min_value {
-                                return false;
-                            }
-                        }
-                    }
-                    // Check wallet age
-                    "wallet_age_days" => {
-                        let wallet_age = context
-                            .aggregates
-                            .get("wallet_age_days")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
-
-                        if let Some(min_value) = constraint.get("min").and_then(|v| v.as_u64()) {
-                            if wallet_age < min_value {
-                                return false;
-                            }
-                        }
-                    }
-                    _ => {
-                        // Unknown condition key, skip
-                        tracing::warn!("Unknown condition key: {}", condition_key);
-                    }
-                }
-            }
-            true
-        }
-        _ => false,
+/// Current and longest runs of consecutive active days.
+///
+/// The current streak still counts when the last active day is yesterday, so it is
+/// not reported as broken before the owner had a chance to act today.
+fn streaks(daily_counts: &BTreeMap<u64, u64>, today: u64) -> (u64, u64) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<u64> = None;
+    for (&day, _) in daily_counts.iter().filter(|(_, count)| **count > 0) {
+        run = match previous {
+            Some(last) if day == last + 1 => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day);
     }
-}
-
-fn categorize_activity(events: &[OwnerActivityEvent]) -> (Vec<ActionEvent>, HashSet<u64>) {
-    let mut by_action: HashMap<String, ActionEvent> = HashMap::new();
-    let mut unique_days: HashSet<u64> = HashSet::new();
-
-    for event in events {
-        // Track unique days (convert timestamp to day number)
-        if let Some(ts) = event.timestamp {
-            let micros = ts.micros();
-            let secs = micros / 1_000_000;
-            let days = secs / 86400; // seconds per day
-            unique_days.insert(days);
-        }
-
-        match &event.kind {
-            ActivityKind::SystemTransfer { .. } => {
-                accumulate_action(&mut by_action, "system_transfer", event.timestamp);
-            }
-            ActivityKind::UserOperation { application_id, .. } => {
-                let specific_key = format!("user_operation:{}", application_id);
-                accumulate_action(&mut by_action, &specific_key, event.timestamp);
-                accumulate_action(&mut by_action, "user_operation", event.timestamp);
-            }
-            ActivityKind::CreateApplication { module_id } => {
-                accumulate_action(&mut by_action, "create_application", event.timestamp);
-                let specific_key = format!("create_application:{}", module_id);
-                accumulate_action(&mut by_action, &specific_key, event.timestamp);
-            }
-        }
-    }
-
-    (by_action.into_values().collect(), unique_days)
-}
-
-fn accumulate_action(
-    actions: &mut HashMap<String, ActionEvent>,
-    key: &str,
-    timestamp: Option<Timestamp>,
-) {
-    let entry = actions.entry(key.to_string()).or_insert(ActionEvent {
-        action_type: key.to_string(),
-        count: 0,
-        last_seen: None,
-    });
-
-    entry.count += 1;
-
-    entry.last_seen = timestamp.map(|ts| ts.to_string());
+    let current = match previous {
+        Some(last) if last + 1 >= today && last <= today => run,
+        _ => 0,
+    };
+    (current, longest)
 }
 
 fn compute_aggregates(
     actions: &[ActionEvent],
-    unique_days: usize,
+    daily_counts: &BTreeMap<u64, u64>,
+    today: u64,
     wallet_age_days: u64,
+    transferred: Amount,
 ) -> HashMap<String, serde_json::Value> {
+    let unique_days = daily_counts.len();
+    let distinct_apps = actions
+        .iter()
+        .filter(|event| event.action_type.starts_with("user_operation:"))
+        .count();
+    // Whole tokens; fractions never decide a threshold written in tokens.
+    let transferred_tokens =
+        u64::try_from(u128::from(transferred) / u128::from(Amount::ONE)).unwrap_or(u64::MAX);
+    let (current_streak, longest_streak) = streaks(daily_counts, today);
     let mut aggregates = HashMap::new();
-    let total_actions: u64 = actions.iter().map(|event| event.count).sum();
+    // Per-application keys (`user_operation:<app_id>`) repeat their generic
+    // counterpart, so only the generic keys add up to one count per operation.
+    let total_actions: u64 = actions
+        .iter()
+        .filter(|event| !event.action_type.contains(':'))
+        .map(|event| event.count)
+        .sum();
     aggregates.insert(
         "total_actions".to_string(),
         serde_json::json!(total_actions),
     );
+    aggregates.insert(
+        "total_transactions".to_string(),
+        serde_json::json!(total_actions),
+    );
     aggregates.insert(
         "unique_active_days".to_string(),
         serde_json::json!(unique_days),
@@ -468,14 +442,31 @@ fn compute_aggregates(
         "wallet_age_days".to_string(),
         serde_json::json!(wallet_age_days),
     );
+    aggregates.insert(
+        "current_streak_days".to_string(),
+        serde_json::json!(current_streak),
+    );
+    aggregates.insert(
+        "longest_streak_days".to_string(),
+        serde_json::json!(longest_streak),
+    );
+    aggregates.insert(
+        "distinct_apps".to_string(),
+        serde_json::json!(distinct_apps),
+    );
+    aggregates.insert(
+        "transferred_tokens".to_string(),
+        serde_json::json!(transferred_tokens),
+    );
     aggregates
 }
 
 /// LLM-based scoring with fallback to rule-based
 pub async fn evaluate_rules_with_llm(
     config: &AppConfig,
+    rules: &RuleSet,
     context: &ObservationContext,
-) -> anyhow::Result<AchievementResult> {
+) -> AchievementResult {
     // Try LLM first if configured
     if let Some(ref openai_config) = config.openai {
         match try_llm_scoring(openai_config, context).await {
@@ -485,7 +476,7 @@ pub async fn evaluate_rules_with_llm(
                     achievement_count = result.achievements.len(),
                     "LLM scoring successful"
                 );
-                return Ok(result);
+                return result;
             }
             Err(err) => {
                 tracing::warn!(
@@ -498,7 +489,7 @@ pub async fn evaluate_rules_with_llm(
 
     // Fallback to rule-based scoring
     tracing::info!("Using rule-based scoring");
-    evaluate_rules(config, context).await
+    apply_rules(rules, context)
 }
 
 #[cfg(feature = "openai")]
@@ -527,3 +518,81 @@ async fn try_llm_scoring(
     )
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(active: &[u64]) -> BTreeMap<u64, u64> {
+        active.iter().map(|day| (*day, 1)).collect()
+    }
+
+    fn context(daily_counts: BTreeMap<u64, u64>, today: u64) -> ObservationContext {
+        ObservationContext {
+            passport_id: String::new(),
+            owner: String::new(),
+            actions: Vec::new(),
+            aggregates: HashMap::new(),
+            daily_counts,
+            today,
+        }
+    }
+
+    #[test]
+    fn streak_runs_through_today() {
+        assert_eq!(streaks(&days(&[10, 11, 12]), 12), (3, 3));
+    }
+
+    #[test]
+    fn streak_ending_yesterday_still_counts() {
+        assert_eq!(streaks(&days(&[10, 11, 12]), 13), (3, 3));
+        assert_eq!(streaks(&days(&[10, 11, 12]), 14), (0, 3));
+    }
+
+    #[test]
+    fn current_and_longest_streaks_differ() {
+        assert_eq!(streaks(&days(&[1, 2, 3, 4, 10, 11]), 11), (2, 4));
+        assert_eq!(streaks(&days(&[1, 2, 3, 4, 10, 11]), 20), (0, 4));
+    }
+
+    #[test]
+    fn idle_days_break_streaks() {
+        let mut daily_counts = days(&[5, 7]);
+        daily_counts.insert(6, 0);
+        assert_eq!(streaks(&daily_counts, 7), (1, 1));
+        assert_eq!(streaks(&BTreeMap::new(), 7), (0, 0));
+    }
+
+    #[test]
+    fn windows_include_today_and_stop_at_their_first_day() {
+        let daily_counts = BTreeMap::from([(90, 1), (91, 2), (99, 3), (100, 4)]);
+        let context = context(daily_counts, 100);
+
+        assert_eq!(context.transactions_in_window(1), 4);
+        assert_eq!(context.active_days_in_window(1), 1);
+        assert_eq!(context.transactions_in_window(2), 7);
+        assert_eq!(context.active_days_in_window(2), 2);
+        // Day 91 is the first day of a 10-day window ending on day 100, day 90 is not.
+        assert_eq!(context.transactions_in_window(10), 9);
+        assert_eq!(context.active_days_in_window(10), 3);
+        assert_eq!(context.transactions_in_window(11), 10);
+        assert_eq!(context.active_days_in_window(11), 4);
+    }
+
+    #[test]
+    fn windows_ignore_later_days_and_clamp_at_epoch() {
+        let daily_counts = BTreeMap::from([(0, 1), (2, 1), (3, 5)]);
+        let context = context(daily_counts, 2);
+
+        assert_eq!(context.transactions_in_window(30), 2);
+        assert_eq!(context.active_days_in_window(30), 2);
+        assert_eq!(context.transactions_in_window(1), 1);
+    }
+
+    #[test]
+    fn day_boundary_is_utc_midnight() {
+        let midnight = 86_400 * 1_000_000;
+        assert_eq!(day_number(Timestamp::from(midnight - 1)), 0);
+        assert_eq!(day_number(Timestamp::from(midnight)), 1);
+    }
+}
@@ -35,7 +35,11 @@ pub async fn llm_scoring_ollama(
     model: &str,
     context: &ObservationContext,
 ) -> Result<AchievementResult> {
-    let total_transactions: u64 = context.actions.iter().map(|e| e.count).sum();
+    let total_transactions = context
+        .aggregates
+        .get("total_transactions")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
     
     let prompt = format!(
         r#"Analyze blockchain wallet activity. Follow these EXACT scoring rules:
@@ -0,0 +1,758 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use linera_base::{
+    data_types::BlockHeight,
+    identifiers::{AccountOwner, ChainId},
+};
+use serde::{Deserialize, Serialize};
+use sled::{
+    transaction::{ConflictableTransactionError, TransactionError},
+    Transactional,
+};
+
+use crate::scoring::ActivityAggregates;
+
+const PASSPORTS_TREE: &str = "passports";
+const CHECKPOINTS_TREE: &str = "checkpoints";
+const AGGREGATES_TREE: &str = "aggregates";
+const OWNER_CHAINS_TREE: &str = "owner_chains";
+const LEAD_CHAINS_TREE: &str = "lead_chains";
+const META_TREE: &str = "meta";
+const FORMAT_KEY: &str = "format";
+/// Bumped whenever a persisted aggregate type changes shape. Aggregates and
+/// checkpoints are re-derived from the indexer, so a store written in another
+/// format drops them instead of migrating.
+const FORMAT_VERSION: u32 = 2;
+
+/// Embedded key-value store kept under `AppConfig::storage_path`.
+///
+/// The oracle uses it as its own record of what was pushed on-chain, so it does not
+/// have to trust the eventually consistent `allPassports` read, and a restart picks
+/// up where the previous run stopped.
+#[derive(Clone)]
+pub struct AgentStore {
+    db: sled::Db,
+    passports: sled::Tree,
+    checkpoints: sled::Tree,
+    aggregates: sled::Tree,
+    /// Chains on which each owner has signed at least one indexed block.
+    owner_chains: sled::Tree,
+    /// Chains that swept transfers lead to, keyed by chain id.
+    lead_chains: sled::Tree,
+}
+
+/// Last block height of a chain already folded into the owner aggregates.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChainCheckpoint {
+    pub height: BlockHeight,
+    /// `false` once any part of the chain history was skipped (paging cap hit).
+    pub complete: bool,
+}
+
+/// Everything the agent remembers about one passport, keyed by hex token id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassportRecord {
+    /// Highest block height already taken into account when scoring, per chain.
+    pub cursors: BTreeMap<ChainId, BlockHeight>,
+    /// Last update the contract accepted.
+    pub submitted: Option<SubmittedUpdate>,
+    /// Update sent but not yet acknowledged; survives a crash mid-submission.
+    pub pending: Option<PendingSubmission>,
+    /// Holding of the passport that `submitted` and `pending` refer to.
+    pub epoch: Option<PassportEpoch>,
+}
+
+/// What the on-chain score and achievements belong to. When it changes, the chain
+/// may have dropped what we pushed, so our own record of it no longer applies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassportEpoch {
+    pub owner: AccountOwner,
+    /// Mint time of the passport. A burned token id is never minted again, but
+    /// this keeps a record from outliving the passport it was written for.
+    pub created_at: u64,
+    /// Number of ownership transfers; a transfer may reset score and achievements.
+    pub transfers: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmittedUpdate {
+    pub score: u64,
+    /// Achievement codes.
+    pub achievements: BTreeSet<String>,
+    /// Passport revision right after the update was applied.
+    pub revision: u64,
+    /// Unix time in seconds.
+    pub submitted_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSubmission {
+    /// Score the passport will have once the update is applied.
+    pub target_score: u64,
+    pub score_increase: u64,
+    /// Achievement codes.
+    pub achievements: Vec<String>,
+    /// Revision the update was computed against, sent as `expected_revision`.
+    pub base_revision: u64,
+    /// Unix time in seconds.
+    pub created_at: u64,
+    /// Set once the node accepted the operation: until this Unix time the update may
+    /// still be on its way to the passport chain, so it is not recomputed.
+    pub wait_until: Option<u64>,
+}
+
+/// The parts of an on-chain passport that settle a pending update.
+#[derive(Debug, Clone, Default)]
+pub struct ChainView {
+    pub revision: u64,
+    pub score: u64,
+    /// Codes of the achievements issued by this oracle.
+    pub issued_codes: BTreeSet<String>,
+}
+
+impl PassportRecord {
+    /// Best known on-chain score: the GraphQL value, or what we pushed and the read at
+    /// `chain_revision` may not show yet. Past that point the chain is authoritative.
+    pub fn known_score(&self, chain_revision: u64, chain_score: u64) -> u64 {
+        let mut known = chain_score;
+        if let Some(submitted) = self.submitted.as_ref() {
+            if chain_revision < submitted.revision {
+                known = known.max(submitted.score);
+            }
+        }
+        if let Some(pending) = self.pending.as_ref() {
+            if chain_revision <= pending.base_revision {
+                known = known.max(pending.target_score);
+            }
+        }
+        known
+    }
+
+    /// Achievements that are on-chain, or pushed by us and possibly not visible in
+    /// the read at `chain_revision` yet.
+    pub fn known_achievements<'a>(
+        &'a self,
+        chain_revision: u64,
+        chain_achievements: impl IntoIterator<Item = &'a String>,
+    ) -> BTreeSet<String> {
+        let mut known: BTreeSet<String> = chain_achievements.into_iter().cloned().collect();
+        if let Some(submitted) = &self.submitted {
+            if chain_revision < submitted.revision {
+                known.extend(submitted.achievements.iter().cloned());
+            }
+        }
+        if let Some(pending) = &self.pending {
+            if chain_revision <= pending.base_revision {
+                known.extend(pending.achievements.iter().cloned());
+            }
+        }
+        known
+    }
+
+    /// Forgets our updates when the passport moved to another holding: the score
+    /// and achievements they added may be gone from the chain.
+    pub fn sync_epoch(&mut self, epoch: PassportEpoch) {
+        if self.epoch.as_ref().is_some_and(|known| *known != epoch) {
+            self.submitted = None;
+            self.pending = None;
+        }
+        self.epoch = Some(epoch);
+    }
+
+    /// Settles a pending update against what the chain holds now.
+    ///
+    /// The revision also moves on other oracles' updates, so a revision past
+    /// `base_revision` alone proves nothing. The update counts as
+    /// applied only when the chain carries all of its codes issued by this oracle
+    /// and at least its target score. Otherwise it was rejected as stale or never
+    /// arrived, and it is dropped so its codes and score are computed again from
+    /// the chain. While the revision has not moved, an accepted update is kept
+    /// until `wait_until`, as it may still be in flight.
+    pub fn reconcile_pending(&mut self, chain: &ChainView, now: u64) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+        if chain.revision <= pending.base_revision {
+            if pending
+                .wait_until
+                .is_some_and(|wait_until| now < wait_until)
+            {
+                return;
+            }
+            self.pending = None;
+            return;
+        }
+        let applied = chain.score >= pending.target_score
+            && pending
+                .achievements
+                .iter()
+                .all(|code| chain.issued_codes.contains(code));
+        if applied {
+            self.confirm_pending();
+        } else {
+            self.pending = None;
+        }
+    }
+
+    /// Revision the passport reached after our last confirmed update.
+    pub fn submitted_revision(&self) -> u64 {
+        self.submitted.as_ref().map_or(0, |s| s.revision)
+    }
+
+    /// Moves the pending update into `submitted`.
+    pub fn confirm_pending(&mut self) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        let submitted = self.submitted.get_or_insert_with(SubmittedUpdate::default);
+        submitted.score = submitted.score.max(pending.target_score);
+        submitted.achievements.extend(pending.achievements);
+        submitted.revision = submitted.revision.max(pending.base_revision + 1);
+        submitted.submitted_at = unix_now();
+    }
+}
+
+impl AgentStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let db = sled::open(path)
+            .with_context(|| format!("failed to open agent store at {}", path.display()))?;
+        Self::from_db(db)
+    }
+
+    /// Store in a throwaway database that is deleted when dropped.
+    #[cfg(test)]
+    pub(crate) fn temporary() -> Result<Self> {
+        let db = sled::Config::new()
+            .temporary(true)
+            .open()
+            .context("failed to open temporary agent store")?;
+        Self::from_db(db)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self> {
+        let passports = db
+            .open_tree(PASSPORTS_TREE)
+            .context("failed to open passports tree")?;
+        let checkpoints = db
+            .open_tree(CHECKPOINTS_TREE)
+            .context("failed to open checkpoints tree")?;
+        let aggregates = db
+            .open_tree(AGGREGATES_TREE)
+            .context("failed to open aggregates tree")?;
+        let owner_chains = db
+            .open_tree(OWNER_CHAINS_TREE)
+            .context("failed to open owner chains tree")?;
+        let lead_chains = db
+            .open_tree(LEAD_CHAINS_TREE)
+            .context("failed to open lead chains tree")?;
+        let meta = db
+            .open_tree(META_TREE)
+            .context("failed to open meta tree")?;
+
+        let format = meta
+            .get(FORMAT_KEY)
+            .context("failed to read store format")?
+            .and_then(|bytes| bcs::from_bytes::<u32>(&bytes).ok());
+        if format != Some(FORMAT_VERSION) {
+            tracing::info!(
+                found = ?format,
+                expected = FORMAT_VERSION,
+                "Agent store format changed, re-indexing chain activity"
+            );
+            aggregates.clear().context("failed to clear aggregates")?;
+            checkpoints.clear().context("failed to clear checkpoints")?;
+            let bytes = bcs::to_bytes(&FORMAT_VERSION).context("failed to encode store format")?;
+            meta.insert(FORMAT_KEY, bytes)
+                .context("failed to write store format")?;
+            db.flush().context("failed to flush agent store")?;
+        }
+
+        Ok(Self {
+            db,
+            passports,
+            checkpoints,
+            aggregates,
+            owner_chains,
+            lead_chains,
+        })
+    }
+
+    pub fn passport(&self, passport_id: &str) -> Result<PassportRecord> {
+        let Some(bytes) = self
+            .passports
+            .get(passport_id)
+            .context("failed to read passport record")?
+        else {
+            return Ok(PassportRecord::default());
+        };
+        // The chain revision makes the record re-derivable, so an unreadable record
+        // (e.g. written by an older agent version) is reset instead of failing the passport.
+        match bcs::from_bytes(&bytes) {
+            Ok(record) => Ok(record),
+            Err(err) => {
+                tracing::warn!(
+                    passport_id = %passport_id,
+                    error = %err,
+                    "Unreadable passport record, starting from an empty one"
+                );
+                Ok(PassportRecord::default())
+            }
+        }
+    }
+
+    pub async fn save_passport(&self, passport_id: &str, record: &PassportRecord) -> Result<()> {
+        let bytes = bcs::to_bytes(record).context("failed to encode passport record")?;
+        self.passports
+            .insert(passport_id, bytes)
+            .context("failed to write passport record")?;
+        self.db
+            .flush_async()
+            .await
+            .context("failed to flush agent store")?;
+        Ok(())
+    }
+
+    pub fn checkpoint(&self, chain_id: &ChainId) -> Result<Option<ChainCheckpoint>> {
+        let Some(bytes) = self
+            .checkpoints
+            .get(chain_id.to_string())
+            .context("failed to read chain checkpoint")?
+        else {
+            return Ok(None);
+        };
+        let checkpoint = bcs::from_bytes(&bytes)
+            .with_context(|| format!("corrupted checkpoint for chain {chain_id}"))?;
+        Ok(Some(checkpoint))
+    }
+
+    pub fn aggregates(
+        &self,
+        owner: &AccountOwner,
+        chain_id: &ChainId,
+    ) -> Result<ActivityAggregates> {
+        let Some(bytes) = self
+            .aggregates
+            .get(aggregates_key(owner, chain_id))
+            .context("failed to read owner aggregates")?
+        else {
+            return Ok(ActivityAggregates::default());
+        };
+        bcs::from_bytes(&bytes)
+            .with_context(|| format!("corrupted aggregates for {owner} on {chain_id}"))
+    }
+
+    /// Chains on which `owner` was seen signing blocks.
+    pub fn owner_chains(&self, owner: &AccountOwner) -> Result<BTreeSet<ChainId>> {
+        let Some(bytes) = self
+            .owner_chains
+            .get(owner.to_string())
+            .context("failed to read owner chains")?
+        else {
+            return Ok(BTreeSet::new());
+        };
+        bcs::from_bytes(&bytes).with_context(|| format!("corrupted chain list for {owner}"))
+    }
+
+    /// Every chain recorded in the owner to chains table.
+    pub fn discovered_chains(&self) -> Result<BTreeSet<ChainId>> {
+        let mut chains = BTreeSet::new();
+        for entry in self.owner_chains.iter() {
+            let (_, bytes) = entry.context("failed to scan owner chains")?;
+            let owner_chains: BTreeSet<ChainId> =
+                bcs::from_bytes(&bytes).context("corrupted owner chain list")?;
+            chains.extend(owner_chains);
+        }
+        Ok(chains)
+    }
+
+    /// Chains found through transfers from a signer to their own account there.
+    pub fn lead_chains(&self) -> Result<BTreeSet<ChainId>> {
+        let mut chains = BTreeSet::new();
+        for key in self.lead_chains.iter().keys() {
+            let key = key.context("failed to scan lead chains")?;
+            let chain_id = std::str::from_utf8(&key)
+                .ok()
+                .and_then(|key| ChainId::from_str(key).ok())
+                .context("corrupted lead chain key")?;
+            chains.insert(chain_id);
+        }
+        Ok(chains)
+    }
+
+    /// Remembers chains that swept transfers lead to. It is written before the
+    /// batch that found them, so a crash in between only repeats the write.
+    pub fn record_lead_chains(&self, chains: &BTreeSet<ChainId>) -> Result<()> {
+        for chain_id in chains {
+            self.lead_chains
+                .insert(chain_id.to_string(), Vec::new())
+                .context("failed to write lead chain")?;
+        }
+        Ok(())
+    }
+
+    /// Merges freshly indexed totals into the stored aggregates, records the
+    /// signers in the owner to chains table and moves the chain checkpoint
+    /// forward, in one transaction.
+    ///
+    /// Either everything is written or nothing is, so a crash never folds the
+    /// same operations twice.
+    pub async fn apply_chain_batch(
+        &self,
+        chain_id: &ChainId,
+        checkpoint: ChainCheckpoint,
+        deltas: &BTreeMap<AccountOwner, ActivityAggregates>,
+    ) -> Result<()> {
+        let checkpoint_bytes = bcs::to_bytes(&checkpoint).context("failed to encode checkpoint")?;
+        let result: Result<(), TransactionError<anyhow::Error>> = (
+            &self.aggregates,
+            &self.checkpoints,
+            &self.owner_chains,
+        )
+            .transaction(|(aggregates, checkpoints, owner_chains)| {
+                for (owner, delta) in deltas {
+                    let key = aggregates_key(owner, chain_id);
+                    let mut stored: ActivityAggregates = match aggregates.get(&key)? {
+                        Some(bytes) => bcs::from_bytes(&bytes).map_err(|err| {
+                            ConflictableTransactionError::Abort(anyhow!(
+                                "corrupted aggregates for {owner} on {chain_id}: {err}"
+                            ))
+                        })?,
+                        None => ActivityAggregates::default(),
+                    };
+                    stored.merge(delta);
+                    let bytes = bcs::to_bytes(&stored).map_err(|err| {
+                        ConflictableTransactionError::Abort(anyhow!(
+                            "failed to encode aggregates: {err}"
+                        ))
+                    })?;
+                    aggregates.insert(key.as_bytes(), bytes)?;
+
+                    let owner_key = owner.to_string();
+                    let mut chains: BTreeSet<ChainId> = match owner_chains.get(&owner_key)? {
+                        Some(bytes) => bcs::from_bytes(&bytes).map_err(|err| {
+                            ConflictableTransactionError::Abort(anyhow!(
+                                "corrupted chain list for {owner}: {err}"
+                            ))
+                        })?,
+                        None => BTreeSet::new(),
+                    };
+                    if chains.insert(*chain_id) {
+                        let bytes = bcs::to_bytes(&chains).map_err(|err| {
+                            ConflictableTransactionError::Abort(anyhow!(
+                                "failed to encode chain list: {err}"
+                            ))
+                        })?;
+                        owner_chains.insert(owner_key.as_bytes(), bytes)?;
+                    }
+                }
+                checkpoints.insert(chain_id.to_string().as_bytes(), checkpoint_bytes.clone())?;
+                Ok(())
+            });
+        match result {
+            Ok(()) => {}
+            Err(TransactionError::Abort(err)) => return Err(err),
+            Err(TransactionError::Storage(err)) => {
+                return Err(anyhow::Error::from(err).context("failed to apply chain batch"))
+            }
+        }
+
+        self.db
+            .flush_async()
+            .await
+            .context("failed to flush agent store")?;
+        Ok(())
+    }
+}
+
+fn aggregates_key(owner: &AccountOwner, chain_id: &ChainId) -> String {
+    format!("{owner}/{chain_id}")
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ActionTally;
+
+    const CHAIN: &str = "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65";
+    const OWNER: &str = "0x5487b70625ce71f7ee29154ad32aefa1c526cb483bdb783dea2e1d17bc497844";
+
+    fn pending(base_revision: u64, wait_until: Option<u64>) -> PassportRecord {
+        PassportRecord {
+            pending: Some(PendingSubmission {
+                target_score: 120,
+                score_increase: 20,
+                achievements: vec!["ACTIVE_USER".to_string()],
+                base_revision,
+                created_at: 1_000,
+                wait_until,
+            }),
+            ..PassportRecord::default()
+        }
+    }
+
+    fn chain(revision: u64, score: u64, codes: &[&str]) -> ChainView {
+        ChainView {
+            revision,
+            score,
+            issued_codes: codes.iter().map(|code| code.to_string()).collect(),
+        }
+    }
+
+    fn submitted(revision: u64) -> SubmittedUpdate {
+        SubmittedUpdate {
+            score: 150,
+            achievements: BTreeSet::from(["EARLY".to_string()]),
+            revision,
+            submitted_at: 0,
+        }
+    }
+
+    fn epoch(owner: &str) -> PassportEpoch {
+        PassportEpoch {
+            owner: AccountOwner::from_str(owner).unwrap(),
+            created_at: 1,
+            transfers: 0,
+        }
+    }
+
+    fn delta(count: u64) -> BTreeMap<AccountOwner, ActivityAggregates> {
+        let mut aggregates = ActivityAggregates::default();
+        aggregates.actions.insert(
+            "user_operation".to_string(),
+            ActionTally {
+                count,
+                last_seen: None,
+            },
+        );
+        BTreeMap::from([(AccountOwner::from_str(OWNER).unwrap(), aggregates)])
+    }
+
+    #[test]
+    fn known_values_include_pushed_updates() {
+        let mut record = pending(4, Some(2_000));
+        record.submitted = Some(submitted(4));
+        assert_eq!(record.known_score(3, 100), 150);
+        assert_eq!(record.known_score(4, 100), 120);
+        assert_eq!(record.known_score(4, 200), 200);
+
+        let chain = ["OTHER".to_string()];
+        let known = record.known_achievements(3, &chain);
+        assert_eq!(known.len(), 3);
+        assert!(known.contains("ACTIVE_USER") && known.contains("EARLY"));
+    }
+
+    #[test]
+    fn chain_past_our_updates_is_authoritative() {
+        // The chain dropped what we pushed, e.g. a transfer reset the score.
+        let mut record = pending(4, Some(2_000));
+        record.submitted = Some(submitted(4));
+        assert_eq!(record.known_score(5, 0), 0);
+
+        let chain = ["OTHER".to_string()];
+        let known = record.known_achievements(5, &chain);
+        assert_eq!(known, BTreeSet::from(["OTHER".to_string()]));
+    }
+
+    #[test]
+    fn new_epoch_forgets_our_updates() {
+        let mut record = pending(4, Some(2_000));
+        record.submitted = Some(submitted(4));
+        record.sync_epoch(epoch(OWNER));
+        assert!(record.pending.is_some() && record.submitted.is_some());
+
+        record.sync_epoch(epoch(OWNER));
+        assert!(record.pending.is_some() && record.submitted.is_some());
+
+        let other = format!("0x{}", "11".repeat(32));
+        record.sync_epoch(epoch(&other));
+        assert!(record.pending.is_none() && record.submitted.is_none());
+        assert_eq!(record.known_score(0, 0), 0);
+        assert_eq!(record.epoch, Some(epoch(&other)));
+    }
+
+    #[test]
+    fn transfer_back_to_the_same_owner_forgets_our_updates() {
+        // A -> B -> A between two scans, with `TransferableWithScoreReset`.
+        let mut record = pending(4, Some(2_000));
+        record.submitted = Some(submitted(4));
+        record.sync_epoch(epoch(OWNER));
+        record.sync_epoch(PassportEpoch {
+            transfers: 2,
+            ..epoch(OWNER)
+        });
+        assert!(record.pending.is_none() && record.submitted.is_none());
+    }
+
+    #[test]
+    fn reminted_passport_forgets_our_updates() {
+        let mut record = pending(4, Some(2_000));
+        record.sync_epoch(epoch(OWNER));
+        record.sync_epoch(PassportEpoch {
+            created_at: 2,
+            ..epoch(OWNER)
+        });
+        assert!(record.pending.is_none());
+    }
+
+    #[test]
+    fn rejected_update_is_forgotten() {
+        // In its challenge window the update shows up as pending on-chain.
+        let mut record = pending(4, Some(2_000));
+        record.reconcile_pending(&chain(5, 120, &["ACTIVE_USER"]), 1_500);
+        assert_eq!(record.submitted_revision(), 5);
+
+        // Rejecting it keeps the revision but drops its codes and score.
+        assert_eq!(record.known_score(5, 100), 100);
+        assert!(record.known_achievements(5, &[]).is_empty());
+    }
+
+    #[test]
+    fn unmoved_revision_keeps_update_in_flight() {
+        let mut record = pending(4, Some(2_000));
+        record.reconcile_pending(&chain(4, 100, &[]), 1_500);
+        assert!(record.pending.is_some());
+        assert!(record.submitted.is_none());
+    }
+
+    #[test]
+    fn unmoved_revision_drops_update_after_wait() {
+        let mut record = pending(4, Some(2_000));
+        record.reconcile_pending(&chain(4, 100, &[]), 2_000);
+        assert!(record.pending.is_none());
+        assert!(record.submitted.is_none());
+
+        let mut never_accepted = pending(4, None);
+        never_accepted.reconcile_pending(&chain(4, 100, &[]), 0);
+        assert!(never_accepted.pending.is_none());
+    }
+
+    #[test]
+    fn applied_update_is_confirmed() {
+        let mut record = pending(4, Some(2_000));
+        record.reconcile_pending(&chain(5, 120, &["ACTIVE_USER"]), 1_500);
+        assert!(record.pending.is_none());
+        let submitted = record.submitted.expect("update confirmed");
+        assert_eq!(submitted.score, 120);
+        assert!(submitted.achievements.contains("ACTIVE_USER"));
+        assert_eq!(submitted.revision, 5);
+    }
+
+    #[test]
+    fn moved_revision_without_our_update_drops_it() {
+        // Another oracle's update moved the revision.
+        let mut missing_code = pending(4, Some(2_000));
+        missing_code.reconcile_pending(&chain(5, 150, &["OTHER"]), 1_500);
+        assert!(missing_code.pending.is_none());
+        assert!(missing_code.submitted.is_none());
+
+        let mut low_score = pending(4, Some(2_000));
+        low_score.reconcile_pending(&chain(6, 110, &["ACTIVE_USER"]), 1_500);
+        assert!(low_score.pending.is_none());
+        assert!(low_score.submitted.is_none());
+    }
+
+    #[test]
+    fn confirm_keeps_the_highest_values() {
+        let mut record = pending(2, None);
+        record.submitted = Some(SubmittedUpdate {
+            score: 200,
+            ..submitted(9)
+        });
+        record.confirm_pending();
+        assert!(record.pending.is_none());
+        let submitted = record.submitted.unwrap();
+        assert_eq!(submitted.score, 200);
+        assert_eq!(submitted.revision, 9);
+        assert_eq!(submitted.achievements.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn passport_record_round_trips() {
+        let store = AgentStore::temporary().unwrap();
+        assert!(store.passport("aa").unwrap().pending.is_none());
+
+        let mut record = pending(3, Some(10));
+        record.sync_epoch(epoch(OWNER));
+        store.save_passport("aa", &record).await.unwrap();
+        let loaded = store.passport("aa").unwrap();
+        assert_eq!(loaded.epoch, Some(epoch(OWNER)));
+        let pending = loaded.pending.unwrap();
+        assert_eq!(pending.target_score, 120);
+        assert_eq!(pending.base_revision, 3);
+        assert_eq!(pending.wait_until, Some(10));
+
+        store.passports.insert("bb", vec![0xff]).unwrap();
+        assert!(store.passport("bb").unwrap().pending.is_none());
+    }
+
+    #[tokio::test]
+    async fn chain_batches_accumulate() {
+        let store = AgentStore::temporary().unwrap();
+        let chain_id = ChainId::from_str(CHAIN).unwrap();
+        let owner = AccountOwner::from_str(OWNER).unwrap();
+
+        for (height, count) in [(3, 2), (8, 5)] {
+            let checkpoint = ChainCheckpoint {
+                height: BlockHeight(height),
+                complete: true,
+            };
+            store
+                .apply_chain_batch(&chain_id, checkpoint, &delta(count))
+                .await
+                .unwrap();
+        }
+
+        let aggregates = store.aggregates(&owner, &chain_id).unwrap();
+        assert_eq!(aggregates.actions["user_operation"].count, 7);
+        assert_eq!(
+            store.checkpoint(&chain_id).unwrap().unwrap().height,
+            BlockHeight(8)
+        );
+        assert_eq!(
+            store.owner_chains(&owner).unwrap(),
+            BTreeSet::from([chain_id])
+        );
+    }
+
+    #[tokio::test]
+    async fn format_change_drops_derived_trees() {
+        let store = AgentStore::temporary().unwrap();
+        let chain_id = ChainId::from_str(CHAIN).unwrap();
+        let owner = AccountOwner::from_str(OWNER).unwrap();
+        let checkpoint = ChainCheckpoint {
+            height: BlockHeight(3),
+            complete: true,
+        };
+        store
+            .apply_chain_batch(&chain_id, checkpoint, &delta(2))
+            .await
+            .unwrap();
+        store.save_passport("aa", &pending()).await.unwrap();
+
+        let same_format = AgentStore::from_db(store.db.clone()).unwrap();
+        assert!(same_format.checkpoint(&chain_id).unwrap().is_some());
+
+        let meta = store.db.open_tree(META_TREE).unwrap();
+        meta.insert(FORMAT_KEY, bcs::to_bytes(&1u32).unwrap())
+            .unwrap();
+        let reopened = AgentStore::from_db(store.db.clone()).unwrap();
+        assert!(reopened.checkpoint(&chain_id).unwrap().is_none());
+        let aggregates = reopened.aggregates(&owner, &chain_id).unwrap();
+        assert!(aggregates.actions.is_empty());
+        assert!(reopened.passport("aa").unwrap().pending.is_some());
+    }
+}
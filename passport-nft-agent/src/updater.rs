@@ -29,6 +29,9 @@ pub trait PayloadSigner: Send + Sync {
         chain_id: ChainId,
         hash: CryptoHash,
     ) -> Result<AccountSignature>;
+
+    /// Account that signs operations on `chain_id`.
+    fn owner(&self, chain_id: ChainId) -> Result<AccountOwner>;
 }
 
 pub struct PassportUpdater {
@@ -59,6 +62,13 @@ impl PassportUpdater {
         }
     }
 
+    /// Account the oracle signs with on `chain_id`; the contract records it as issuer.
+    pub fn signer_owner(&self, chain_id: ChainId) -> Result<AccountOwner> {
+        self.signer.owner(chain_id)
+    }
+
+    /// Runs `UpdateAchievements` on `chain_id`, the oracle's own chain. The contract
+    /// forwards it to `args.target_chain` when the passport lives elsewhere.
     pub async fn submit_update(
         &self,
         chain_id: ChainId,
@@ -70,8 +80,10 @@ impl PassportUpdater {
         tracing::info!(
             request_id = %request_id,
             chain_id = %chain_id,
+            target_chain = ?args.target_chain,
             application_id = %self.application_id,
             token_id = ?args.token_id,
+            expected_revision = args.expected_revision,
             score_increase = args.score_increase,
             achievement_count = args.new_achievements.len(),
             "Submitting update operation to blockchain"
@@ -86,24 +98,46 @@ impl PassportUpdater {
         // Convert token_id bytes to array format for GraphQL
         let token_id_array = args.token_id.id.clone();
 
+        let new_achievements = args
+            .new_achievements
+            .iter()
+            .map(|achievement| {
+                serde_json::json!({
+                    "code": achievement.code,
+                    "points": achievement.points,
+                    "evidenceHash": achievement.evidence_hash,
+                })
+            })
+            .collect::<Vec<_>>();
+
         // Build GraphQL mutation
-        let mutation = format!(
-            r#"
-            mutation {{
+        let mutation = r#"
+            mutation UpdateAchievements(
+                $tokenId: TokenIdInput!
+                $targetChain: ChainId
+                $expectedRevision: Int!
+                $newAchievements: [AchievementInput!]!
+                $scoreIncrease: Int!
+            ) {
                 updateAchievements(
-                    tokenId: {{ id: {:?} }}
-                    newAchievements: {:?}
-                    scoreIncrease: {}
+                    tokenId: $tokenId
+                    targetChain: $targetChain
+                    expectedRevision: $expectedRevision
+                    newAchievements: $newAchievements
+                    scoreIncrease: $scoreIncrease
                 )
-            }}
-            "#,
-            token_id_array,
-            args.new_achievements,
-            args.score_increase
-        );
+            }
+        "#;
 
         let request = serde_json::json!({
-            "query": mutation
+            "query": mutation,
+            "variables": {
+                "tokenId": { "id": token_id_array },
+                "targetChain": args.target_chain.map(|chain_id| chain_id.to_string()),
+                "expectedRevision": args.expected_revision,
+                "newAchievements": new_achievements,
+                "scoreIncrease": args.score_increase,
+            },
         });
 
         let response = self
@@ -153,7 +187,8 @@ impl PassportUpdater {
         tracing::info!(request_id = %request_id, chain_id = %chain_id, application_id = %self.application_id, token_id = ?args.token_id, metadata_uri = %args.metadata_uri, "Submitting mint operation to blockchain");
         let graphql_endpoint = format!("{}/chains/{}/applications/{}", self.linera_rpc_endpoint, chain_id, self.application_id);
         let token_id_array = args.token_id.id.clone();
-        let mutation = format!(r#"mutation {{ mint(tokenId: {{ id: {:?} }} metadataUri: "{}" imageUri: "{}" contentHash: "{}") }}"#, token_id_array, args.metadata_uri, args.image_uri, args.content_hash);
+        let owner = args.owner.map(|owner| format!(r#" owner: "{}""#, owner)).unwrap_or_default();
+        let mutation = format!(r#"mutation {{ mint(tokenId: {{ id: {:?} }} metadataUri: "{}" imageUri: "{}" contentHash: "{}"{}) }}"#, token_id_array, args.metadata_uri, args.image_uri, args.content_hash, owner);
         let request = serde_json::json!({"query": mutation});
         let response = self.http.post(&graphql_endpoint).json(&request).send().await.context("failed to send GraphQL mint request")?;
         let status = response.status();
@@ -186,6 +221,17 @@ impl PayloadSigner for WalletSigner {
         let secret = self.key_for_chain(chain_id)?;
         Ok(secret.sign_prehash(hash))
     }
+
+    fn owner(&self, chain_id: ChainId) -> Result<AccountOwner> {
+        let wallet = Self::load_wallet(&self.wallet_path)?;
+        let chain = wallet
+            .chains
+            .get(&chain_id)
+            .ok_or_else(|| anyhow!("wallet missing chain {chain_id}"))?;
+        chain
+            .owner
+            .ok_or_else(|| anyhow!("chain {chain_id} has no owner recorded in wallet"))
+    }
 }
 
 impl WalletSigner {
@@ -245,14 +291,7 @@ impl WalletSigner {
     }
 
     fn key_for_chain(&self, chain_id: ChainId) -> Result<AccountSecretKey> {
-        let wallet = Self::load_wallet(&self.wallet_path)?;
-        let chain = wallet
-            .chains
-            .get(&chain_id)
-            .ok_or_else(|| anyhow!("wallet missing chain {chain_id}"))?;
-        let owner = chain
-            .owner
-            .ok_or_else(|| anyhow!("chain {chain_id} has no owner recorded in wallet"))?;
+        let owner = self.owner(chain_id)?;
         let secret = self
             .owner_keys
             .get(&owner)
@@ -1,6 +1,8 @@
 #![cfg_attr(target_arch = "wasm32", no_main)]
 
 use anyhow::{bail, ensure, Context, Result};
+use linera_base::data_types::{Amount, TimeDelta};
+use linera_base::identifiers::{Account, AccountOwner, ChainId};
 use linera_sdk::{
     contract,
     linera_base_types::WithContractAbi,
@@ -9,8 +11,15 @@ use linera_sdk::{
 };
 
 use passport_nft::{
-    AddAchievementArgs, AddOracleArgs, IncreaseScoreArgs, MintArgs, Passport, PassportNftAbi,
-    PassportOperation, PassportState, RemoveOracleArgs, TokenId, UpdateArgs,
+    error::PassportError,
+    Achievement, AddAchievementArgs, AddOracleArgs, BurnArgs, IncreaseScoreArgs, MintArgs, Passport,
+    AchievementUpdateOutcome, MigrateArgs, PassportEvent, PassportEventV1, PassportNftAbi, PassportOperation,
+    PassportMessage, PassportParameters, PassportResponse, PassportState, RemoveOracleArgs, RevokeArgs,
+    Tombstone, TombstoneKind, TokenId, OwnershipTransfer, TransferArgs, TransferPolicy, UpdateArgs,
+    MintPolicy, PassportInstantiationArgument, PassportLimits, ProposeAdminArgs, Role, RoleAction,
+    RoleArgs, RoleChange, PauseFlags, SetPauseArgs, AchievementInput, OracleQuorum, ProposalStatus,
+    UpdateProposal, Dispute, DisputeArgs, FinalizeUpdateArgs, PendingUpdate, ResolveDisputeArgs,
+    OracleRecord, OracleStatus, RegisterOracleArgs, SlashOracleArgs, UnbondStakeArgs, Unbonding,
 };
 
 ///
@@ -21,15 +30,18 @@ pub struct PassportContract {
 
 contract!(PassportContract);
 
+#[cfg(test)]
+mod tests;
+
 impl WithContractAbi for PassportContract {
     type Abi = PassportNftAbi;
 }
 
 impl Contract for PassportContract {
-    type Message = ();
-    type InstantiationArgument = ();
-    type Parameters = ();
-    type EventValue = ();
+    type Message = PassportMessage;
+    type InstantiationArgument = PassportInstantiationArgument;
+    type Parameters = PassportParameters;
+    type EventValue = PassportEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         match PassportState::load(runtime.root_view_storage_context()).await {
@@ -42,29 +54,77 @@ impl Contract for PassportContract {
         }
     }
 
-    async fn instantiate(&mut self, _state: Self::InstantiationArgument) {
-        self.runtime.application_parameters();
-        
-        // SECURITY FIX: Set the first authenticated signer as admin
-        // This happens only once when application is created
-        if let Some(creator) = self.runtime.authenticated_signer() {
-            log::info!("Setting application admin to: {:?}", creator);
-            self.state.admin.set(Some(creator));
+    async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
+        let parameters = self.runtime.application_parameters();
+        log::info!("Application parameters: {:?}", parameters);
+        if let Err(error) = parameters.validate() {
+            panic!("invalid application parameters: {error}");
+        }
+        let mut oracles = argument.oracles.clone();
+        oracles.sort();
+        oracles.dedup();
+        assert!(
+            oracles.len() == argument.oracles.len(),
+            "duplicate oracles in instantiation argument"
+        );
+
+        // Администратор из аргумента, иначе подписант создания приложения
+        let signer = self.runtime.authenticated_signer();
+        let admin = argument.admin.or(signer);
+        if let Some(admin) = admin {
+            log::info!("Setting application admin to: {:?}", admin);
+            self.state.admin.set(Some(admin));
+            self.audit(RoleAction::AdminChanged, admin, None, signer);
+            self.emit(PassportEventV1::AdminChanged {
+                previous: None,
+                admin: Some(admin),
+            });
         } else {
-            log::warn!("Application instantiated without authenticated signer - no admin set");
+            log::warn!("Application instantiated without admin or authenticated signer - no admin set");
+        }
+
+        let now = self.runtime.system_time();
+        for oracle in argument.oracles {
+            let record = OracleRecord {
+                oracle,
+                registered_at: now,
+                stake: Amount::ZERO,
+                unbonding: None,
+                submissions: 0,
+                disputes_lost: 0,
+                status: OracleStatus::Active,
+                genesis: true,
+            };
+            self.state
+                .oracles
+                .insert(&oracle, record)
+                .expect("failed to add initial oracle");
+            self.audit(RoleAction::OracleAdded, oracle, None, signer);
+            if let Some(by) = admin {
+                self.emit(PassportEventV1::OracleAdded { oracle, by });
+            }
         }
     }
 
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
-        if let Err(error) = self.try_execute_operation(operation).await {
-            let message = format!("operation failed: {error:#}");
+        match self.try_execute_operation(operation).await {
+            Ok(response) => response,
+            Err(error) => {
+                let message = failure_message("operation failed", &error);
+                log::error!("{message}");
+                panic!("{message}");
+            }
+        }
+    }
+
+    async fn execute_message(&mut self, message: Self::Message) {
+        if let Err(error) = self.try_execute_message(message).await {
+            let message = failure_message("message failed", &error);
             log::error!("{message}");
             panic!("{message}");
         }
     }
 
-    async fn execute_message(&mut self, _message: Self::Message) {}
-
     async fn store(mut self) {
         if let Err(error) = self.state.save().await {
             let message = format!("failed to save state: {error:#}");
@@ -75,21 +135,417 @@ impl Contract for PassportContract {
 }
 
 impl PassportContract {
-    async fn try_execute_operation(&mut self, operation: PassportOperation) -> Result<()> {
+    fn emit(&mut self, event: PassportEventV1) {
+        self.runtime
+            .emit(passport_nft::passport_event_stream(), &PassportEvent::V1(event));
+    }
+
+    async fn try_execute_operation(
+        &mut self,
+        operation: PassportOperation,
+    ) -> Result<PassportResponse> {
         match operation {
-            PassportOperation::Mint(args) => self.mint(args).await,
-            PassportOperation::AddAchievement(args) => self.add_achievement(args).await,
-            PassportOperation::IncreaseScore(args) => self.increase_score(args).await,
-            PassportOperation::UpdateAchievements(args) => self.update_achievements(args).await,
-            PassportOperation::AddOracle(args) => self.add_oracle(args).await,
-            PassportOperation::RemoveOracle(args) => self.remove_oracle(args).await,
+            PassportOperation::Mint(args) => {
+                let args = self.prepare_mint(args).await?;
+                let creator_chain = self.runtime.application_creator_chain_id();
+                if self.runtime.chain_id() != creator_chain {
+                    // Роли и отозванные паспорта известны только цепочке-создателю
+                    return self.forward(creator_chain, PassportMessage::RegisterMint(args));
+                }
+                self.ensure_may_mint(&args).await?;
+                self.mint(args)?
+            }
+            PassportOperation::AddAchievement(args) => {
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward_owner_operation(
+                        chain_id,
+                        PassportMessage::AddAchievement(args),
+                    );
+                }
+                self.add_achievement(args).await?
+            }
+            PassportOperation::IncreaseScore(args) => {
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward_owner_operation(
+                        chain_id,
+                        PassportMessage::IncreaseScore(args),
+                    );
+                }
+                self.increase_score(args).await?
+            }
+            PassportOperation::UpdateAchievements(args) => {
+                let signer = self.authorized_oracle().await?;
+                // Пауза на цепочке-создателе останавливает и пересылку на другие цепочки
+                self.ensure_not_paused(
+                    |flags| flags.oracle_updates,
+                    PassportError::OracleUpdatesPaused,
+                )?;
+                let quorum = self.runtime.application_parameters().quorum;
+                let remote_chain = self.remote_chain(args.target_chain);
+                // Предложение хранится, а пересылка уходит без проверки на этой
+                // цепочке, поэтому проверяем заранее
+                if quorum.required > 1 || remote_chain.is_some() {
+                    validate_update(&args, &self.limits())?;
+                }
+                // Повторный голос того же оракула в реестре не учитывается
+                let (args, approvals) = if quorum.required > 1 {
+                    match self.vote_update(signer, args, &quorum).await? {
+                        Vote::Pending(status) => {
+                            self.record_submission(signer).await?;
+                            return Ok(PassportResponse::Proposed(status));
+                        }
+                        Vote::Repeated(status) => return Ok(PassportResponse::Proposed(status)),
+                        Vote::Reached { args, approvals } => (args, approvals),
+                    }
+                } else {
+                    (args, Vec::new())
+                };
+                self.record_submission(signer).await?;
+                if let Some(chain_id) = remote_chain {
+                    let message = PassportMessage::OracleUpdate { args, approvals };
+                    return self.forward(chain_id, message);
+                }
+                let outcome = self.update_achievements(signer, approvals, args).await?;
+                return Ok(PassportResponse::Achievements(outcome));
+            }
+            PassportOperation::AddOracle(args) => self.add_oracle(args).await?,
+            PassportOperation::RemoveOracle(args) => self.remove_oracle(args).await?,
+            PassportOperation::Migrate(args) => {
+                let destination = args.destination;
+                self.migrate(args).await?;
+                return Ok(PassportResponse::Forwarded(destination));
+            }
+            PassportOperation::Burn(args) => {
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward_owner_operation(
+                        chain_id,
+                        PassportMessage::Burn(args),
+                    );
+                }
+                self.burn(args).await?
+            }
+            PassportOperation::Transfer(args) => {
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward_owner_operation(
+                        chain_id,
+                        PassportMessage::Transfer(args),
+                    );
+                }
+                if let Some(creator_chain) = self.start_transfer(args).await? {
+                    return Ok(PassportResponse::Forwarded(creator_chain));
+                }
+            }
+            PassportOperation::Revoke(args) => {
+                let moderator = self.authorized(Role::Moderator).await?;
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward(chain_id, PassportMessage::Revoke(args));
+                }
+                self.revoke(moderator, args).await?
+            }
+            PassportOperation::GrantRole(args) => self.grant_role(args).await?,
+            PassportOperation::RevokeRole(args) => self.revoke_role(args).await?,
+            PassportOperation::ProposeAdmin(args) => self.propose_admin(args)?,
+            PassportOperation::AcceptAdmin => self.accept_admin()?,
+            PassportOperation::DisputeUpdate(args) => {
+                let reviewer = self.is_reviewer().await?;
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    let message = PassportMessage::DisputeUpdate { args, reviewer };
+                    return self.forward(chain_id, message);
+                }
+                self.dispute_update(args, reviewer).await?
+            }
+            PassportOperation::ResolveDispute(args) => {
+                let admin = self.authorized_admin()?;
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward(chain_id, PassportMessage::ResolveDispute(args));
+                }
+                self.resolve_dispute(admin, args).await?
+            }
+            PassportOperation::FinalizeUpdate(args) => {
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward(chain_id, PassportMessage::FinalizeUpdate(args));
+                }
+                self.finalize_update(args).await?
+            }
+            PassportOperation::RegisterOracle(args) => self.register_oracle(args).await?,
+            PassportOperation::UnbondStake(args) => self.unbond_stake(args).await?,
+            PassportOperation::WithdrawStake => self.withdraw_stake().await?,
+            PassportOperation::SlashOracle(args) => self.slash_oracle(args).await?,
+            PassportOperation::SetPause(args) => {
+                let admin = self.authorized_admin()?;
+                if let Some(chain_id) = self.remote_chain(args.target_chain) {
+                    return self.forward(chain_id, PassportMessage::SetPause(args));
+                }
+                self.set_pause(admin, args.flags);
+            }
+        }
+        Ok(PassportResponse::Ok)
+    }
+
+    async fn try_execute_message(&mut self, message: PassportMessage) -> Result<()> {
+        // Сообщение вернулось отправителю: цепочка паспорта его отклонила.
+        // На цепочке-отправителе ничего не менялось, откатывать нечего.
+        if self.runtime.message_is_bouncing() == Some(true) {
+            if let PassportMessage::MigratePassport { passport, .. } = message {
+                if passport.owner_chain == self.runtime.chain_id() {
+                    return self.restore_migrated(passport).await;
+                }
+                // Отказ цепочки назначения вернулся на цепочку-создатель,
+                // пересылавшую паспорт: отправляем его на исходную цепочку
+                let source = passport.owner_chain;
+                self.runtime
+                    .prepare_message(PassportMessage::ReturnMigrated(passport))
+                    .send_to(source);
+                return Ok(());
+            }
+            log::warn!("Passport message rejected by the target chain: {:?}", message);
+            return Ok(());
+        }
+
+        match message {
+            PassportMessage::MigratePassport {
+                passport,
+                destination,
+            } => self.route_migrated(passport, destination).await?,
+            PassportMessage::OracleUpdate { args, approvals } => {
+                // Список оракулов ведется на цепочке-создателе, поэтому доверяем
+                // только обновлениям, пересланным оттуда
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "oracle updates are only accepted from the application creator chain"
+                );
+                let Some(signer) = self.runtime.authenticated_signer() else {
+                    bail!("oracle update message requires a forwarded signer");
+                };
+                self.update_achievements(signer, approvals, args).await?;
+            }
+            PassportMessage::AddAchievement(args) => self.add_achievement(args).await?,
+            PassportMessage::IncreaseScore(args) => self.increase_score(args).await?,
+            PassportMessage::Transfer(args) => {
+                self.start_transfer(args).await?;
+            }
+            PassportMessage::RegisterTransfer(args) => {
+                ensure!(
+                    self.runtime.chain_id() == self.runtime.application_creator_chain_id(),
+                    "transfers are approved on the application creator chain"
+                );
+                let Some(origin) = self.runtime.message_origin_chain_id() else {
+                    bail!("transfer request has no origin chain");
+                };
+                // Здесь собраны отозванные владельцы со всех цепочек
+                self.ensure_not_paused(
+                    |flags| flags.owner_mutations,
+                    PassportError::OwnerMutationsPaused,
+                )?;
+                self.ensure_not_revoked(args.new_owner).await?;
+                self.runtime
+                    .prepare_message(PassportMessage::TransferApproved(args))
+                    .with_authentication()
+                    .with_tracking()
+                    .send_to(origin);
+            }
+            PassportMessage::TransferApproved(args) => {
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "transfer approvals are only accepted from the application creator chain"
+                );
+                // Пока шло одобрение, паспорт могли передать или уничтожить
+                self.transfer(args).await?;
+            }
+            PassportMessage::Burn(args) => self.burn(args).await?,
+            PassportMessage::Revoke(args) => {
+                // Модераторы известны только цепочке-создателю
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "revocations are only accepted from the application creator chain"
+                );
+                let Some(moderator) = self.runtime.authenticated_signer() else {
+                    bail!("revocation message requires a forwarded signer");
+                };
+                self.revoke(moderator, args).await?;
+            }
+            PassportMessage::DisputeUpdate { args, reviewer } => {
+                // Роль подписанта проверяется только на цепочке-создателе
+                let from_creator = self.runtime.message_origin_chain_id()
+                    == Some(self.runtime.application_creator_chain_id());
+                self.dispute_update(args, reviewer && from_creator).await?;
+            }
+            PassportMessage::ResolveDispute(args) => {
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "dispute resolutions are only accepted from the application creator chain"
+                );
+                let Some(admin) = self.runtime.authenticated_signer() else {
+                    bail!("dispute resolution message requires a forwarded signer");
+                };
+                self.resolve_dispute(admin, args).await?;
+            }
+            PassportMessage::FinalizeUpdate(args) => self.finalize_update(args).await?,
+            PassportMessage::DisputeLost { oracle } => {
+                ensure!(
+                    self.runtime.chain_id() == self.runtime.application_creator_chain_id(),
+                    "dispute results are recorded on the application creator chain"
+                );
+                self.record_dispute_lost(oracle).await?;
+            }
+            PassportMessage::SetPause(args) => {
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "pause changes are only accepted from the application creator chain"
+                );
+                let Some(admin) = self.runtime.authenticated_signer() else {
+                    bail!("pause message requires a forwarded signer");
+                };
+                self.set_pause(admin, args.flags);
+            }
+            PassportMessage::RegisterMint(args) => {
+                ensure!(
+                    self.runtime.chain_id() == self.runtime.application_creator_chain_id(),
+                    "mints are approved on the application creator chain"
+                );
+                let Some(origin) = self.runtime.message_origin_chain_id() else {
+                    bail!("mint request has no origin chain");
+                };
+                // Здесь собраны удаленные паспорта и отозванные владельцы со всех цепочек,
+                // а пауза выпуска на цепочке-создателе действует для всех цепочек
+                self.ensure_not_paused(|flags| flags.mint, PassportError::MintPaused)?;
+                self.ensure_may_mint(&args).await?;
+                let owner = self.mint_owner(&args)?;
+                self.ensure_not_removed(&args.token_id, owner).await?;
+                self.runtime
+                    .prepare_message(PassportMessage::MintApproved(args))
+                    .with_authentication()
+                    .with_tracking()
+                    .send_to(origin);
+            }
+            PassportMessage::MintApproved(args) => {
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "mint approvals are only accepted from the application creator chain"
+                );
+                // Пока шло одобрение, здесь могли выпустить паспорт
+                let owner = self.mint_owner(&args)?;
+                self.check_mint_target(&args, owner).await?;
+                self.mint(args)?;
+            }
+            PassportMessage::ReturnMigrated(passport) => {
+                let origin = self.runtime.message_origin_chain_id();
+                ensure!(
+                    origin == Some(self.runtime.application_creator_chain_id()),
+                    "returned migrations are only accepted from the application creator chain"
+                );
+                self.restore_migrated(passport).await?;
+            }
+            PassportMessage::RecordTombstone {
+                token_id,
+                tombstone,
+            } => {
+                ensure!(
+                    self.runtime.chain_id() == self.runtime.application_creator_chain_id(),
+                    "removed passports are recorded on the application creator chain"
+                );
+                self.record_tombstone(token_id, tombstone)?;
+            }
         }
+        Ok(())
+    }
+
+    /// Отклоняет операцию, если ее семейство приостановлено на этой цепочке
+    fn ensure_not_paused(
+        &self,
+        paused: fn(&PauseFlags) -> bool,
+        error: PassportError,
+    ) -> Result<()> {
+        if paused(self.state.paused.get()) {
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    /// Меняет флаги паузы; `admin` уже проверен на цепочке-создателе
+    fn set_pause(&mut self, admin: AccountOwner, flags: PauseFlags) {
+        log::warn!("Admin {:?} set pause flags to {:?}", admin, flags);
+        self.state.paused.set(flags);
+        self.emit(PassportEventV1::PauseChanged { flags, by: admin });
+    }
+
+    fn limits(&mut self) -> PassportLimits {
+        self.runtime.application_parameters().limits
+    }
+
+    /// `target_chain`, если это не текущая цепочка
+    fn remote_chain(&mut self, target_chain: Option<ChainId>) -> Option<ChainId> {
+        target_chain.filter(|chain_id| *chain_id != self.runtime.chain_id())
+    }
+
+    /// Пересылает операцию владельца; пауза на этой цепочке останавливает и пересылку
+    fn forward_owner_operation(
+        &mut self,
+        chain_id: ChainId,
+        message: PassportMessage,
+    ) -> Result<PassportResponse> {
+        self.ensure_not_paused(
+            |flags| flags.owner_mutations,
+            PassportError::OwnerMutationsPaused,
+        )?;
+        self.forward(chain_id, message)
+    }
+
+    /// Отправляет операцию на цепочку паспорта с пересылкой подписи. Сообщение
+    /// отслеживается, чтобы отказ цепочки паспорта вернулся отправителю.
+    fn forward(&mut self, chain_id: ChainId, message: PassportMessage) -> Result<PassportResponse> {
+        ensure!(
+            self.runtime.authenticated_signer().is_some(),
+            "forwarding a passport operation requires authentication"
+        );
+        self.runtime
+            .prepare_message(message)
+            .with_authentication()
+            .with_tracking()
+            .send_to(chain_id);
+        Ok(PassportResponse::Forwarded(chain_id))
+    }
+
+    /// Владелец выпускаемого паспорта: `owner` из аргументов или подписант
+    fn mint_owner(&mut self, args: &MintArgs) -> Result<AccountOwner> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("mint requires an authenticated owner");
+        };
+        Ok(args.owner.unwrap_or(signer))
     }
 
-    async fn mint(&mut self, args: MintArgs) -> Result<()> {
-        let Some(owner) = self.runtime.authenticated_signer() else {
+    /// Проверки выпуска на цепочке будущего паспорта; владелец фиксируется в `owner`
+    async fn prepare_mint(&mut self, mut args: MintArgs) -> Result<MintArgs> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
             bail!("mint requires an authenticated owner");
         };
+        match self.runtime.application_parameters().mint_policy {
+            MintPolicy::Open | MintPolicy::MintersOnly => {}
+            MintPolicy::ChainOwnersOnly => {
+                let ownership = self.runtime.chain_ownership();
+                ensure!(
+                    ownership.super_owners.contains(&signer)
+                        || ownership.owners.contains_key(&signer),
+                    "only owners of this chain may mint passports here"
+                );
+            }
+            MintPolicy::Closed => bail!("minting is closed for this application"),
+        }
+        let owner = args.owner.unwrap_or(signer);
+        self.check_mint_target(&args, owner).await?;
+        args.owner = Some(owner);
+        Ok(args)
+    }
+
+    /// Паспорт `args.token_id` для `owner` можно создать на этой цепочке
+    async fn check_mint_target(&mut self, args: &MintArgs, owner: AccountOwner) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.mint, PassportError::MintPaused)?;
         let token_exists = self
             .state
             .passports
@@ -97,6 +553,14 @@ impl PassportContract {
             .await
             .context("failed to check existing passport")?;
         ensure!(!token_exists, "passport already exists");
+        let migrated = self
+            .state
+            .migrated_to
+            .contains_key(&args.token_id)
+            .await
+            .context("failed to check migrated passports")?;
+        ensure!(!migrated, "passport was migrated to another chain");
+        self.ensure_not_removed(&args.token_id, owner).await?;
         let owner_has_passport = self
             .state
             .owner_index
@@ -104,8 +568,34 @@ impl PassportContract {
             .await
             .context("failed to check owner index")?;
         ensure!(!owner_has_passport, "owner already has a passport");
-        validate_uris(&args.metadata_uri, &args.image_uri, &args.content_hash)?;
+        validate_uris(
+            &args.metadata_uri,
+            &args.image_uri,
+            &args.content_hash,
+            &self.limits(),
+        )
+    }
+
+    /// Выпуск для другого аккаунта или под `MintersOnly` требует роли `Minter`;
+    /// роли известны только цепочке-создателю
+    async fn ensure_may_mint(&mut self, args: &MintArgs) -> Result<()> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("mint requires an authenticated owner");
+        };
+        let owner = args.owner.unwrap_or(signer);
+        let policy = self.runtime.application_parameters().mint_policy;
+        if owner != signer || policy == MintPolicy::MintersOnly {
+            ensure!(
+                self.has_role(signer, Role::Minter).await?,
+                "minting requires the Minter role"
+            );
+        }
+        Ok(())
+    }
 
+    /// Создает проверенный и одобренный паспорт на текущей цепочке
+    fn mint(&mut self, args: MintArgs) -> Result<()> {
+        let owner = self.mint_owner(&args)?;
         let owner_chain = self.runtime.chain_id();
 
         let passport = Passport {
@@ -118,15 +608,34 @@ impl PassportContract {
             content_hash: args.content_hash,
             achievements: Vec::new(),
             score: 0,
+            self_claimed_achievements: Vec::new(),
+            self_reported_score: 0,
+            revision: 0,
+            transfers: Vec::new(),
+            pending_update: None,
         };
 
+        self.insert_passport(passport)?;
+
+        self.emit(PassportEventV1::Minted {
+            token_id: args.token_id,
+            owner,
+            owner_chain,
+        });
+        Ok(())
+    }
+
+    /// Добавляет паспорт в хранилище, индекс владельцев и счетчик выпуска
+    fn insert_passport(&mut self, passport: Passport) -> Result<()> {
+        let token_id = passport.token_id.clone();
+        let owner = passport.owner;
         self.state
             .passports
-            .insert(&args.token_id, passport)
+            .insert(&token_id, passport)
             .context("failed to store passport")?;
         self.state
             .owner_index
-            .insert(&owner, args.token_id.clone())
+            .insert(&owner, token_id)
             .context("failed to update owner index")?;
 
         let total_supply = self.state.total_supply.get_mut();
@@ -134,183 +643,1571 @@ impl PassportContract {
         Ok(())
     }
 
-    async fn mutate_passport<F>(&mut self, token_id: TokenId, mutator: F) -> Result<()>
-    where
-        F: FnOnce(&mut Passport) -> Result<()>,
-    {
-        let chain_id = self.runtime.chain_id();
+    /// Удаляет паспорт из хранилища, индекса владельцев и счетчика выпуска
+    fn remove_passport(&mut self, passport: &Passport) -> Result<()> {
+        self.state
+            .passports
+            .remove(&passport.token_id)
+            .context("failed to remove passport")?;
+        self.state
+            .owner_index
+            .remove(&passport.owner)
+            .context("failed to update owner index")?;
+
+        let total_supply = self.state.total_supply.get_mut();
+        *total_supply = total_supply.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Отклоняет `token_id` уничтоженного или отозванного паспорта и владельца,
+    /// чей паспорт отозван модерацией
+    async fn ensure_not_removed(&self, token_id: &TokenId, owner: AccountOwner) -> Result<()> {
+        let removed = self
+            .state
+            .tombstones
+            .contains_key(token_id)
+            .await
+            .context("failed to check removed passports")?;
+        ensure!(!removed, "passport was burned or revoked");
+        self.ensure_not_revoked(owner).await
+    }
+
+    /// Отклоняет владельца, чей паспорт отозван модерацией
+    async fn ensure_not_revoked(&self, owner: AccountOwner) -> Result<()> {
+        let revoked = self
+            .state
+            .revoked_owners
+            .contains_key(&owner)
+            .await
+            .context("failed to check revoked owners")?;
+        ensure!(!revoked, "owner's passport was revoked by moderation");
+        Ok(())
+    }
+
+    /// Заносит удаленный паспорт в `tombstones`, а владельца отозванного - в
+    /// `revoked_owners`
+    fn record_tombstone(&mut self, token_id: TokenId, tombstone: Tombstone) -> Result<()> {
+        if tombstone.kind == TombstoneKind::Revoked {
+            self.state
+                .revoked_owners
+                .insert(&tombstone.owner, token_id.clone())
+                .context("failed to record revoked owner")?;
+        }
+        self.state
+            .tombstones
+            .insert(&token_id, tombstone)
+            .context("failed to record removed passport")
+    }
+
+    /// Удаляет паспорт насовсем и оставляет запись о нем здесь и на
+    /// цепочке-создателе, которая проверяет по ней выпуск и перенос на других цепочках
+    fn bury_passport(&mut self, passport: &Passport, tombstone: Tombstone) -> Result<()> {
+        self.remove_passport(passport)?;
+        self.record_tombstone(passport.token_id.clone(), tombstone.clone())?;
+        let creator_chain = self.runtime.application_creator_chain_id();
+        if self.runtime.chain_id() != creator_chain {
+            let message = PassportMessage::RecordTombstone {
+                token_id: passport.token_id.clone(),
+                tombstone: tombstone.clone(),
+            };
+            self.runtime.prepare_message(message).send_to(creator_chain);
+        }
+        self.emit(PassportEventV1::Removed {
+            token_id: passport.token_id.clone(),
+            tombstone,
+        });
+        Ok(())
+    }
+
+    /// Владелец уничтожает свой паспорт на его цепочке
+    async fn burn(&mut self, args: BurnArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
         let passport = self
             .state
             .passports
-            .get_mut(&token_id)
+            .get(&args.token_id)
             .await
             .context("failed to load passport")?
             .context("passport not found")?;
-
         ensure!(
-            passport.owner_chain == chain_id,
+            passport.owner_chain == self.runtime.chain_id(),
             "passport anchored on different chain"
         );
+        let Some(owner) = self
+            .runtime
+            .authenticated_signer()
+            .filter(|signer| *signer == passport.owner)
+        else {
+            bail!("only owner may burn passport");
+        };
 
-        ensure!(
-            Some(passport.owner) == self.runtime.authenticated_signer(),
-            "only owner may mutate passport"
-        );
-
-        mutator(passport)
+        log::info!("Owner {:?} burned passport {:?}", owner, args.token_id);
+        let tombstone = Tombstone {
+            owner,
+            kind: TombstoneKind::Burned,
+            reason: None,
+            removed_by: owner,
+            removed_at: self.runtime.system_time(),
+        };
+        self.bury_passport(&passport, tombstone)
     }
 
-    async fn add_achievement(&mut self, args: AddAchievementArgs) -> Result<()> {
-        ensure!(
-            args.achievement.len() <= 256,
-            "achievement text exceeds 256 chars"
+    /// Отзывает паспорт по решению модерации. Роль `moderator` уже проверена
+    /// на цепочке-создателе.
+    async fn revoke(&mut self, moderator: AccountOwner, args: RevokeArgs) -> Result<()> {
+        let passport = self
+            .state
+            .passports
+            .get(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+
+        log::info!(
+            "Moderator {:?} revoked passport {:?}: {:?}",
+            moderator,
+            args.token_id,
+            args.reason
         );
-        self.mutate_passport(args.token_id, |passport| {
-            passport.achievements.push(args.achievement);
-            Ok(())
-        })
-        .await
+        let tombstone = Tombstone {
+            owner: passport.owner,
+            kind: TombstoneKind::Revoked,
+            reason: Some(args.reason),
+            removed_by: moderator,
+            removed_at: self.runtime.system_time(),
+        };
+        self.bury_passport(&passport, tombstone)
     }
 
-    async fn increase_score(&mut self, args: IncreaseScoreArgs) -> Result<()> {
-        ensure!(args.amount > 0, "score increment must be positive");
-        self.mutate_passport(args.token_id, |passport| {
-            passport.score = passport
-                .score
-                .checked_add(args.amount)
-                .context("score overflow")?;
-            Ok(())
-        })
-        .await
+    /// Начинает передачу на цепочке паспорта
+    ///
+    /// Отозванные владельцы известны только цепочке-создателю, поэтому на других
+    /// цепочках передача выполняется после ее одобрения (`RegisterTransfer`).
+    /// Возвращает цепочку-создатель, если запрос отправлен туда.
+    async fn start_transfer(&mut self, args: TransferArgs) -> Result<Option<ChainId>> {
+        let creator_chain = self.runtime.application_creator_chain_id();
+        if self.runtime.chain_id() == creator_chain {
+            self.transfer(args).await?;
+            return Ok(None);
+        }
+        self.check_transfer(&args).await?;
+        self.forward(creator_chain, PassportMessage::RegisterTransfer(args))?;
+        Ok(Some(creator_chain))
     }
 
-    async fn update_achievements(&mut self, args: UpdateArgs) -> Result<()> {
-        // CRITICAL FIX #1: Проверка авторизации оракула
-        let Some(signer) = self.runtime.authenticated_signer() else {
-            bail!("update_achievements requires authentication");
-        };
-
-        let is_authorized = self
+    /// Передачу `args` можно выполнить на этой цепочке
+    async fn check_transfer(&mut self, args: &TransferArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
+        let policy = self.runtime.application_parameters().transfer_policy;
+        ensure!(
+            policy != TransferPolicy::Soulbound,
+            "passports are soulbound in this application"
+        );
+        let passport = self
             .state
-            .authorized_oracles
-            .contains(&signer)
+            .passports
+            .get(&args.token_id)
             .await
-            .context("failed to check oracle authorization")?;
-
+            .context("failed to load passport")?
+            .context("passport not found")?;
         ensure!(
-            is_authorized,
-            "only authorized oracles can update achievements"
+            passport.owner_chain == self.runtime.chain_id(),
+            "passport anchored on different chain, set target_chain to {}",
+            passport.owner_chain
         );
-
-        // CRITICAL FIX #2: Проверка лимита достижений
         ensure!(
-            args.new_achievements.len() <= 100,
-            "too many achievements in single update"
+            Some(passport.owner) == self.runtime.authenticated_signer(),
+            "only owner may transfer passport"
         );
+        self.ensure_not_revoked(args.new_owner).await?;
+        let owner_has_passport = self
+            .state
+            .owner_index
+            .contains_key(&args.new_owner)
+            .await
+            .context("failed to check owner index")?;
+        ensure!(!owner_has_passport, "new owner already has a passport");
+        Ok(())
+    }
 
-        for achievement in &args.new_achievements {
-            ensure!(
-                achievement.len() <= 256,
-                "achievement text exceeds 256 chars"
-            );
-        }
+    /// Передает паспорт новому владельцу согласно `transfer_policy`
+    async fn transfer(&mut self, args: TransferArgs) -> Result<()> {
+        self.check_transfer(&args).await?;
+        let policy = self.runtime.application_parameters().transfer_policy;
+        let score_reset = policy == TransferPolicy::TransferableWithScoreReset;
+        let new_owner = args.new_owner;
 
-        // Загрузить паспорт без проверки владельца (оракул не владелец)
+        let at = self.runtime.system_time();
+        let (from, revision) = self
+            .mutate_passport(args.token_id.clone(), |passport| {
+                let from = passport.owner;
+                if score_reset {
+                    passport.achievements.clear();
+                    passport.score = 0;
+                    passport.self_claimed_achievements.clear();
+                    passport.self_reported_score = 0;
+                    passport.pending_update = None;
+                }
+                passport.owner = new_owner;
+                // Обновления оракула, посчитанные для прежнего владельца, устаревают
+                passport.revision += 1;
+                passport.transfers.push(OwnershipTransfer {
+                    from,
+                    to: new_owner,
+                    at,
+                    score_reset,
+                });
+                Ok((from, passport.revision))
+            })
+            .await?;
+
+        self.state
+            .owner_index
+            .remove(&from)
+            .context("failed to update owner index")?;
+        self.state
+            .owner_index
+            .insert(&new_owner, args.token_id.clone())
+            .context("failed to update owner index")?;
+
+        log::info!(
+            "Passport {:?} transferred from {:?} to {:?}",
+            args.token_id,
+            from,
+            new_owner
+        );
+        self.emit(PassportEventV1::Transferred {
+            token_id: args.token_id,
+            from,
+            to: new_owner,
+            score_reset,
+            revision,
+        });
+        Ok(())
+    }
+
+    /// Переносит паспорт владельца на цепочку `destination`
+    ///
+    /// Паспорт удаляется здесь до отправки сообщения, поэтому он никогда не живет
+    /// на двух цепочках сразу. Отказ цепочки назначения возвращает сообщение, и
+    /// паспорт восстанавливается (`restore_migrated`).
+    async fn migrate(&mut self, args: MigrateArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
         let chain_id = self.runtime.chain_id();
+        ensure!(
+            args.destination != chain_id,
+            "passport is already on this chain"
+        );
         let passport = self
             .state
             .passports
-            .get_mut(&args.token_id)
+            .get(&args.token_id)
             .await
             .context("failed to load passport")?
             .context("passport not found")?;
-
         ensure!(
             passport.owner_chain == chain_id,
             "passport anchored on different chain"
         );
-
-        // CRITICAL FIX #3: Проверка общего количества достижений
-        let total_achievements = passport.achievements.len() + args.new_achievements.len();
         ensure!(
-            total_achievements <= 500,
-            "total achievements limit (500) exceeded"
+            Some(passport.owner) == self.runtime.authenticated_signer(),
+            "only owner may migrate passport"
         );
 
-        // Обновить достижения
-        passport.achievements.extend(args.new_achievements.clone());
+        // Реестр удаленных паспортов ведет цепочка-создатель, поэтому перенос
+        // идет через нее, а с нее самой - сразу на цепочку назначения
+        let creator_chain = self.runtime.application_creator_chain_id();
+        let next_hop = if chain_id == creator_chain {
+            self.ensure_not_removed(&passport.token_id, passport.owner).await?;
+            args.destination
+        } else {
+            creator_chain
+        };
 
-        // Увеличить скор
-        if args.score_increase > 0 {
-            passport.score = passport
-                .score
-                .checked_add(args.score_increase)
-                .context("score overflow")?;
-        }
+        self.remove_passport(&passport)?;
+        self.state
+            .migrated_to
+            .insert(&args.token_id, args.destination)
+            .context("failed to record migration")?;
 
+        log::info!(
+            "Migrating passport {:?} from {} to {}",
+            args.token_id,
+            chain_id,
+            args.destination
+        );
+        let message = PassportMessage::MigratePassport {
+            passport,
+            destination: args.destination,
+        };
+        self.runtime
+            .prepare_message(message)
+            .with_authentication()
+            .with_tracking()
+            .send_to(next_hop);
         Ok(())
     }
 
-    async fn add_oracle(&mut self, args: AddOracleArgs) -> Result<()> {
-        // SECURITY FIX: Only admin can add oracles
-        let Some(signer) = self.runtime.authenticated_signer() else {
-            bail!("add_oracle requires authentication");
-        };
-        
-        let admin = self.state.admin.get();
+    /// Переносимый паспорт: цепочка-создатель сверяет его с реестром удаленных
+    /// паспортов и пересылает дальше, цепочка назначения принимает его только от нее
+    async fn route_migrated(&mut self, passport: Passport, destination: ChainId) -> Result<()> {
+        let chain_id = self.runtime.chain_id();
+        let creator_chain = self.runtime.application_creator_chain_id();
+        if destination == chain_id {
+            ensure!(
+                chain_id == creator_chain
+                    || self.runtime.message_origin_chain_id() == Some(creator_chain),
+                "migrations are only accepted through the application creator chain"
+            );
+            return self.receive_migrated(passport).await;
+        }
         ensure!(
-            admin.as_ref() == Some(&signer),
-            "only application admin can add oracles"
+            chain_id == creator_chain,
+            "only the application creator chain relays migrations"
         );
-
-        self.state
-            .authorized_oracles
-            .insert(&args.oracle)
-            .context("failed to add oracle")?;
-        
-        log::info!("Admin {:?} added oracle: {:?}", signer, args.oracle);
+        // Отказ возвращает паспорт на исходную цепочку
+        self.ensure_not_paused(
+            |flags| flags.owner_mutations,
+            PassportError::OwnerMutationsPaused,
+        )?;
+        self.ensure_not_removed(&passport.token_id, passport.owner).await?;
+        self.runtime
+            .prepare_message(PassportMessage::MigratePassport {
+                passport,
+                destination,
+            })
+            .with_authentication()
+            .with_tracking()
+            .send_to(destination);
         Ok(())
     }
 
-
-    async fn remove_oracle(&mut self, args: RemoveOracleArgs) -> Result<()> {
-        // SECURITY FIX: Only admin can remove oracles
-        let Some(signer) = self.runtime.authenticated_signer() else {
-            bail!("remove_oracle requires authentication");
-        };
-        
-        let admin = self.state.admin.get();
+    /// Принимает перенесенный паспорт на текущей цепочке
+    async fn receive_migrated(&mut self, mut passport: Passport) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
+        ensure!(
+            Some(passport.owner) == self.runtime.authenticated_signer(),
+            "migration must be signed by the passport owner"
+        );
+        let ownership = self.runtime.chain_ownership();
         ensure!(
-            admin.as_ref() == Some(&signer),
-            "only application admin can remove oracles"
+            ownership.super_owners.contains(&passport.owner)
+                || ownership.owners.contains_key(&passport.owner),
+            "passport owner does not control the destination chain"
         );
+        let token_exists = self
+            .state
+            .passports
+            .contains_key(&passport.token_id)
+            .await
+            .context("failed to check existing passport")?;
+        ensure!(!token_exists, "passport already exists");
+        self.ensure_not_removed(&passport.token_id, passport.owner).await?;
+        let owner_has_passport = self
+            .state
+            .owner_index
+            .contains_key(&passport.owner)
+            .await
+            .context("failed to check owner index")?;
+        ensure!(!owner_has_passport, "owner already has a passport");
 
+        let from = passport.owner_chain;
+        let to = self.runtime.chain_id();
+        passport.owner_chain = to;
+        let token_id = passport.token_id.clone();
+        let owner = passport.owner;
+        self.insert_passport(passport)?;
+        // Паспорт мог уже жить здесь раньше и вернуться
         self.state
-            .authorized_oracles
-            .remove(&args.oracle)
-            .context("failed to remove oracle")?;
-        
-        log::info!("Admin {:?} removed oracle: {:?}", signer, args.oracle);
+            .migrated_to
+            .remove(&token_id)
+            .context("failed to clear migration record")?;
+
+        self.emit(PassportEventV1::Migrated {
+            token_id,
+            owner,
+            from,
+            to,
+        });
         Ok(())
     }
-}
 
-fn validate_uris(metadata_uri: &str, image_uri: &str, content_hash: &str) -> Result<()> {
-    ensure!(
-        metadata_uri.len() <= 256,
-        "metadata_uri must be at most 256 characters"
-    );
-    ensure!(
-        image_uri.len() <= 256,
-        "image_uri must be at most 256 characters"
-    );
-    ensure!(
-        content_hash.len() <= 256,
-        "content_hash must be at most 256 characters"
-    );
-    ensure!(
-        !metadata_uri.is_empty() && !image_uri.is_empty() && !content_hash.is_empty(),
-        "URIs and content hash must be non-empty"
-    );
+    /// Восстанавливает паспорт, перенос которого отклонила цепочка назначения
+    async fn restore_migrated(&mut self, passport: Passport) -> Result<()> {
+        log::warn!(
+            "Migration of passport {:?} was rejected, restoring it on this chain",
+            passport.token_id
+        );
+        self.state
+            .migrated_to
+            .remove(&passport.token_id)
+            .context("failed to clear migration record")?;
+        // Возврат нельзя отклонить, поэтому он не должен падать: если владелец
+        // успел выпустить здесь новый паспорт, индекс остается за новым.
+        let owner_has_passport = self
+            .state
+            .owner_index
+            .contains_key(&passport.owner)
+            .await
+            .context("failed to check owner index")?;
+        if owner_has_passport {
+            log::warn!(
+                "Owner {:?} minted another passport meanwhile, restoring {:?} without indexing it",
+                passport.owner,
+                passport.token_id
+            );
+            let token_id = passport.token_id.clone();
+            self.state
+                .passports
+                .insert(&token_id, passport)
+                .context("failed to store passport")?;
+            *self.state.total_supply.get_mut() += 1;
+            return Ok(());
+        }
+        self.insert_passport(passport)
+    }
+
+    async fn mutate_passport<F, T>(&mut self, token_id: TokenId, mutator: F) -> Result<T>
+    where
+        F: FnOnce(&mut Passport) -> Result<T>,
+    {
+        let chain_id = self.runtime.chain_id();
+        let passport = self
+            .state
+            .passports
+            .get_mut(&token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+
+        ensure!(
+            passport.owner_chain == chain_id,
+            "passport anchored on different chain, set target_chain to {}",
+            passport.owner_chain
+        );
+
+        ensure!(
+            Some(passport.owner) == self.runtime.authenticated_signer(),
+            "only owner may mutate passport"
+        );
+
+        mutator(passport)
+    }
+
+    async fn add_achievement(&mut self, args: AddAchievementArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
+        ensure!(
+            self.runtime
+                .application_parameters()
+                .allow_self_claimed_achievements,
+            "self-claimed achievements are disabled for this application"
+        );
+        validate_achievement(&args.code, args.evidence_hash.as_deref())?;
+        let max_achievements = self.limits().max_achievements as usize;
+        let awarded_at = self.runtime.system_time();
+        let token_id = args.token_id.clone();
+        let achievement = self.mutate_passport(args.token_id, |passport| {
+            ensure!(
+                passport.self_claimed_achievements.len() < max_achievements,
+                "total achievements limit ({max_achievements}) exceeded"
+            );
+            ensure!(
+                !passport
+                    .self_claimed_achievements
+                    .iter()
+                    .any(|achievement| achievement.code == args.code),
+                "achievement already claimed"
+            );
+            // Владелец не может сам себе начислять очки за достижение
+            let achievement = Achievement {
+                code: args.code,
+                points: 0,
+                awarded_at,
+                issuer: passport.owner,
+                approved_by: Vec::new(),
+                evidence_hash: args.evidence_hash,
+            };
+            passport.self_claimed_achievements.push(achievement.clone());
+            Ok(achievement)
+        })
+        .await?;
+
+        self.emit(PassportEventV1::AchievementSelfClaimed {
+            token_id,
+            achievement,
+        });
+        Ok(())
+    }
+
+    async fn increase_score(&mut self, args: IncreaseScoreArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.owner_mutations, PassportError::OwnerMutationsPaused)?;
+        ensure!(
+            self.runtime.application_parameters().allow_self_reported_score,
+            "self-reported score is disabled for this application"
+        );
+        ensure!(args.amount > 0, "score increment must be positive");
+        let token_id = args.token_id.clone();
+        let event = self
+            .mutate_passport(args.token_id, |passport| {
+                // Проверенный скор меняют только оракулы
+                passport.self_reported_score = passport
+                    .self_reported_score
+                    .checked_add(args.amount)
+                    .context("score overflow")?;
+                Ok(score_changed(token_id, passport))
+            })
+            .await?;
+
+        self.emit(event);
+        Ok(())
+    }
+
+    /// Подписант текущей операции, если он авторизованный оракул
+    async fn authorized_oracle(&mut self) -> Result<AccountOwner> {
+        // CRITICAL FIX #1: Проверка авторизации оракула
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("update_achievements requires authentication");
+        };
+
+        ensure!(
+            self.is_eligible_oracle(signer).await?,
+            "only active oracles with sufficient stake can update achievements"
+        );
+        Ok(signer)
+    }
+
+    /// Активный оракул с залогом не меньше `min_stake` или назначенный при создании
+    async fn is_eligible_oracle(&mut self, oracle: AccountOwner) -> Result<bool> {
+        let staking = self.runtime.application_parameters().staking;
+        let record = self
+            .state
+            .oracles
+            .get(&oracle)
+            .await
+            .context("failed to load oracle record")?;
+        Ok(record.is_some_and(|record| record.is_eligible(&staking)))
+    }
+
+    /// Учитывает голос оракула за обновление и возвращает его, когда набран кворум
+    async fn vote_update(
+        &mut self,
+        oracle: AccountOwner,
+        args: UpdateArgs,
+        quorum: &OracleQuorum,
+    ) -> Result<Vote> {
+        let now = self.runtime.system_time();
+        let revision = args.expected_revision;
+        let mut proposals = self
+            .state
+            .proposals
+            .get(&args.token_id)
+            .await
+            .context("failed to load proposals")?
+            .unwrap_or_default();
+        // Ревизию из аргументов задает голосующий, поэтому чистка опирается только
+        // на срок и на ревизию паспорта, если он хранится на этой цепочке
+        let current_revision = self
+            .state
+            .passports
+            .get(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .map(|passport| passport.revision);
+        proposals.retain(|proposal| {
+            proposal.expires_at > now
+                && current_revision.map_or(true, |current| proposal.revision >= current)
+        });
+
+        let index = match proposals
+            .iter()
+            .position(|proposal| proposal.revision == revision && same_update(proposal, &args))
+        {
+            Some(index) => index,
+            None => {
+                ensure!(
+                    !proposals.iter().any(|proposal| proposal.revision == revision
+                        && proposal.approvals.contains(&oracle)),
+                    "oracle already proposed a different update for revision {revision}"
+                );
+                proposals.push(UpdateProposal {
+                    token_id: args.token_id.clone(),
+                    revision,
+                    target_chain: args.target_chain,
+                    new_achievements: args.new_achievements.clone(),
+                    score_increase: args.score_increase,
+                    approvals: Vec::new(),
+                    created_at: now,
+                    expires_at: now.saturating_add(TimeDelta::from_secs(quorum.proposal_ttl_secs)),
+                });
+                proposals.len() - 1
+            }
+        };
+        // Повторная подача тем же оракулом не считается новым голосом
+        let repeated = proposals[index].approvals.contains(&oracle);
+        if !repeated {
+            proposals[index].approvals.push(oracle);
+        }
+
+        // Учитываются только оракулы, допущенные сейчас
+        let mut approvals = 0;
+        for approver in proposals[index].approvals.clone() {
+            if self.is_eligible_oracle(approver).await? {
+                approvals += 1;
+            }
+        }
+        let required = quorum.required;
+        if repeated {
+            self.store_proposals(&args.token_id, proposals)?;
+            return Ok(Vote::Repeated(ProposalStatus {
+                approvals,
+                required,
+            }));
+        }
+        self.emit(PassportEventV1::UpdateProposed {
+            token_id: args.token_id.clone(),
+            revision,
+            oracle,
+            approvals,
+            required,
+        });
+
+        let vote = if approvals >= required {
+            let proposal = proposals.swap_remove(index);
+            proposals.retain(|proposal| proposal.revision > revision);
+            log::info!(
+                "Quorum {}/{} reached for passport {:?} at revision {}",
+                approvals,
+                required,
+                proposal.token_id,
+                revision
+            );
+            Vote::Reached {
+                args: UpdateArgs {
+                    token_id: proposal.token_id,
+                    target_chain: proposal.target_chain,
+                    expected_revision: proposal.revision,
+                    new_achievements: proposal.new_achievements,
+                    score_increase: proposal.score_increase,
+                },
+                approvals: proposal.approvals,
+            }
+        } else {
+            Vote::Pending(ProposalStatus {
+                approvals,
+                required,
+            })
+        };
+
+        self.store_proposals(&args.token_id, proposals)?;
+        Ok(vote)
+    }
+
+    fn store_proposals(
+        &mut self,
+        token_id: &TokenId,
+        proposals: Vec<UpdateProposal>,
+    ) -> Result<()> {
+        if proposals.is_empty() {
+            self.state
+                .proposals
+                .remove(token_id)
+                .context("failed to store proposals")?;
+        } else {
+            self.state
+                .proposals
+                .insert(token_id, proposals)
+                .context("failed to store proposals")?;
+        }
+        Ok(())
+    }
+
+    /// Применяет обновление оракула `signer`, уже прошедшего проверку
+    async fn update_achievements(
+        &mut self,
+        signer: AccountOwner,
+        approvals: Vec<AccountOwner>,
+        args: UpdateArgs,
+    ) -> Result<AchievementUpdateOutcome> {
+        self.ensure_not_paused(|flags| flags.oracle_updates, PassportError::OracleUpdatesPaused)?;
+        let limits = self.limits();
+        validate_update(&args, &limits)?;
+        let challenge_period_secs = self.runtime.application_parameters().challenge_period_secs;
+
+        // Загрузить паспорт без проверки владельца (оракул не владелец)
+        let chain_id = self.runtime.chain_id();
+        let awarded_at = self.runtime.system_time();
+        let passport = self
+            .state
+            .passports
+            .get_mut(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+
+        ensure!(
+            passport.owner_chain == chain_id,
+            "passport anchored on different chain"
+        );
+
+        // Устаревшее или повторно отправленное обновление отклоняется целиком.
+        // Самостоятельные заявления владельца ревизию не меняют, чтобы владелец
+        // не мог сорвать обновления оракула.
+        if args.expected_revision != passport.revision {
+            return Err(anyhow::Error::from(PassportError::StaleUpdate).context(format!(
+                "expected revision {}, passport is at revision {}",
+                args.expected_revision, passport.revision
+            )));
+        }
+        passport.revision += 1;
+
+        let mut events = Vec::new();
+        // Неоспоренное обновление с истекшим окном применяется перед следующим
+        if let Some(pending) = &passport.pending_update {
+            let context = if pending.dispute.is_some() {
+                Some("update is disputed and awaits admin resolution".to_string())
+            } else if pending.challenge_ends_at > awarded_at {
+                Some(format!("challenge period ends at {}", pending.challenge_ends_at))
+            } else {
+                None
+            };
+            if let Some(context) = context {
+                return Err(anyhow::Error::from(PassportError::UnderReview).context(context));
+            }
+            let pending = passport.pending_update.take().expect("checked above");
+            let (_, settled) = apply_update(
+                &args.token_id,
+                passport,
+                pending.achievements,
+                pending.score_increase,
+                &limits,
+            )?;
+            events.extend(settled);
+            events.push(PassportEventV1::UpdateSettled {
+                token_id: args.token_id.clone(),
+                accepted: true,
+                by: None,
+            });
+        }
+
+        let achievements: Vec<Achievement> = args
+            .new_achievements
+            .into_iter()
+            .map(|input| Achievement {
+                code: input.code,
+                points: input.points,
+                awarded_at,
+                issuer: signer,
+                approved_by: approvals.clone(),
+                evidence_hash: input.evidence_hash,
+            })
+            .collect();
+
+        let outcome = if challenge_period_secs > 0 {
+            // Итог считается на копии: достижения и скор меняются только после окна
+            let (outcome, _) = apply_update(
+                &args.token_id,
+                &mut passport.clone(),
+                achievements.clone(),
+                args.score_increase,
+                &limits,
+            )?;
+            let update = PendingUpdate {
+                achievements,
+                score_increase: args.score_increase,
+                issuer: signer,
+                submitted_at: awarded_at,
+                challenge_ends_at: awarded_at
+                    .saturating_add(TimeDelta::from_secs(challenge_period_secs)),
+                dispute: None,
+            };
+            passport.pending_update = Some(update.clone());
+            events.push(PassportEventV1::UpdatePending {
+                token_id: args.token_id.clone(),
+                update,
+                revision: passport.revision,
+            });
+            outcome
+        } else {
+            let (outcome, applied) = apply_update(
+                &args.token_id,
+                passport,
+                achievements,
+                args.score_increase,
+                &limits,
+            )?;
+            events.extend(applied);
+            outcome
+        };
+
+        log::info!(
+            "Oracle {:?} updated passport: granted {:?}, upgraded {:?}, ignored {:?}",
+            signer,
+            outcome.granted,
+            outcome.upgraded,
+            outcome.ignored
+        );
+        for event in events {
+            self.emit(event);
+        }
+        Ok(outcome)
+    }
+
+    /// Может ли подписант оспаривать обновления как администратор или оракул
+    async fn is_reviewer(&mut self) -> Result<bool> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            return Ok(false);
+        };
+        if self.state.admin.get().as_ref() == Some(&signer) {
+            return Ok(true);
+        }
+        self.is_eligible_oracle(signer).await
+    }
+
+    /// Оспаривает обновление на проверке; `reviewer` - подписант является
+    /// администратором или оракулом
+    async fn dispute_update(&mut self, args: DisputeArgs, reviewer: bool) -> Result<()> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("dispute requires authentication");
+        };
+        ensure!(
+            !args.reason.trim().is_empty() && args.reason.len() <= 256,
+            "dispute reason must be 1 to 256 characters"
+        );
+        let chain_id = self.runtime.chain_id();
+        let now = self.runtime.system_time();
+        let passport = self
+            .state
+            .passports
+            .get_mut(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+        ensure!(
+            passport.owner_chain == chain_id,
+            "passport anchored on different chain, set target_chain to {}",
+            passport.owner_chain
+        );
+        ensure!(
+            reviewer || signer == passport.owner,
+            "only the admin, an oracle or the passport owner may dispute an update"
+        );
+        let Some(pending) = passport.pending_update.as_mut() else {
+            bail!("passport has no update under review");
+        };
+        ensure!(pending.dispute.is_none(), "update is already disputed");
+        ensure!(now < pending.challenge_ends_at, "challenge period has ended");
+        ensure!(
+            signer != pending.issuer,
+            "an oracle cannot dispute its own update"
+        );
+
+        let dispute = Dispute {
+            by: signer,
+            reason: args.reason,
+            at: now,
+        };
+        pending.dispute = Some(dispute.clone());
+        log::info!("{:?} disputed update of passport {:?}", signer, args.token_id);
+        self.emit(PassportEventV1::UpdateDisputed {
+            token_id: args.token_id,
+            dispute,
+        });
+        Ok(())
+    }
+
+    /// Решение администратора по оспоренному обновлению; `admin` уже проверен
+    /// на цепочке-создателе
+    async fn resolve_dispute(
+        &mut self,
+        admin: AccountOwner,
+        args: ResolveDisputeArgs,
+    ) -> Result<()> {
+        let limits = self.limits();
+        let chain_id = self.runtime.chain_id();
+        let passport = self
+            .state
+            .passports
+            .get_mut(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+        ensure!(
+            passport.owner_chain == chain_id,
+            "passport anchored on different chain"
+        );
+        let Some(pending) = passport.pending_update.take() else {
+            bail!("passport has no update under review");
+        };
+        ensure!(pending.dispute.is_some(), "update is not disputed");
+        let issuer = pending.issuer;
+
+        let mut events = Vec::new();
+        if args.accept {
+            let (_, applied) = apply_update(
+                &args.token_id,
+                passport,
+                pending.achievements,
+                pending.score_increase,
+                &limits,
+            )?;
+            events = applied;
+        }
+        events.push(PassportEventV1::UpdateSettled {
+            token_id: args.token_id.clone(),
+            accepted: args.accept,
+            by: Some(admin),
+        });
+
+        log::info!(
+            "Admin {:?} resolved dispute for passport {:?}: accepted = {}",
+            admin,
+            args.token_id,
+            args.accept
+        );
+        for event in events {
+            self.emit(event);
+        }
+        if !args.accept {
+            self.record_dispute_lost(issuer).await?;
+        }
+        Ok(())
+    }
+
+    /// Применяет неоспоренное обновление после окна оспаривания
+    async fn finalize_update(&mut self, args: FinalizeUpdateArgs) -> Result<()> {
+        self.ensure_not_paused(|flags| flags.oracle_updates, PassportError::OracleUpdatesPaused)?;
+        let limits = self.limits();
+        let chain_id = self.runtime.chain_id();
+        let now = self.runtime.system_time();
+        let passport = self
+            .state
+            .passports
+            .get_mut(&args.token_id)
+            .await
+            .context("failed to load passport")?
+            .context("passport not found")?;
+        ensure!(
+            passport.owner_chain == chain_id,
+            "passport anchored on different chain, set target_chain to {}",
+            passport.owner_chain
+        );
+        let Some(pending) = &passport.pending_update else {
+            bail!("passport has no update under review");
+        };
+        ensure!(
+            pending.dispute.is_none(),
+            "update is disputed and awaits admin resolution"
+        );
+        ensure!(
+            pending.challenge_ends_at <= now,
+            "challenge period has not ended"
+        );
+        let pending = passport.pending_update.take().expect("checked above");
+        let (_, mut events) = apply_update(
+            &args.token_id,
+            passport,
+            pending.achievements,
+            pending.score_increase,
+            &limits,
+        )?;
+        events.push(PassportEventV1::UpdateSettled {
+            token_id: args.token_id,
+            accepted: true,
+            by: None,
+        });
+        for event in events {
+            self.emit(event);
+        }
+        Ok(())
+    }
+
+    /// Подписант операции, если это администратор приложения
+    fn authorized_admin(&mut self) -> Result<AccountOwner> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("admin operation requires authentication");
+        };
+        ensure!(
+            self.state.admin.get().as_ref() == Some(&signer),
+            "only application admin can do this"
+        );
+        Ok(signer)
+    }
+
+    /// Есть ли у аккаунта роль; администратор обладает всеми ролями
+    async fn has_role(&self, account: AccountOwner, role: Role) -> Result<bool> {
+        if self.state.admin.get().as_ref() == Some(&account) {
+            return Ok(true);
+        }
+        let roles = self
+            .state
+            .roles
+            .get(&account)
+            .await
+            .context("failed to load roles")?;
+        Ok(roles.is_some_and(|roles| roles.contains(&role)))
+    }
+
+    /// Подписант операции, если у него есть роль `role`
+    async fn authorized(&mut self, role: Role) -> Result<AccountOwner> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("{role:?} operation requires authentication");
+        };
+        ensure!(
+            self.has_role(signer, role).await?,
+            "signer does not have the {role:?} role"
+        );
+        Ok(signer)
+    }
+
+    /// Добавляет запись в журнал ролей
+    fn audit(
+        &mut self,
+        action: RoleAction,
+        account: AccountOwner,
+        role: Option<Role>,
+        by: Option<AccountOwner>,
+    ) -> RoleChange {
+        let change = RoleChange {
+            action,
+            account,
+            role,
+            by,
+            at: self.runtime.system_time(),
+        };
+        self.state.role_log.push(change.clone());
+        change
+    }
+
+    async fn grant_role(&mut self, args: RoleArgs) -> Result<()> {
+        let admin = self.authorized_admin()?;
+        let mut roles = self
+            .state
+            .roles
+            .get(&args.account)
+            .await
+            .context("failed to load roles")?
+            .unwrap_or_default();
+        ensure!(
+            !roles.contains(&args.role),
+            "account already has the {:?} role",
+            args.role
+        );
+        roles.push(args.role);
+        self.state
+            .roles
+            .insert(&args.account, roles)
+            .context("failed to store roles")?;
+
+        log::info!("Admin {:?} granted {:?} to {:?}", admin, args.role, args.account);
+        let change = self.audit(RoleAction::Granted, args.account, Some(args.role), Some(admin));
+        self.emit(PassportEventV1::RoleChanged { change });
+        Ok(())
+    }
+
+    async fn revoke_role(&mut self, args: RoleArgs) -> Result<()> {
+        let admin = self.authorized_admin()?;
+        let mut roles = self
+            .state
+            .roles
+            .get(&args.account)
+            .await
+            .context("failed to load roles")?
+            .unwrap_or_default();
+        ensure!(
+            roles.contains(&args.role),
+            "account does not have the {:?} role",
+            args.role
+        );
+        roles.retain(|role| *role != args.role);
+        if roles.is_empty() {
+            self.state
+                .roles
+                .remove(&args.account)
+                .context("failed to store roles")?;
+        } else {
+            self.state
+                .roles
+                .insert(&args.account, roles)
+                .context("failed to store roles")?;
+        }
+
+        log::info!("Admin {:?} revoked {:?} from {:?}", admin, args.role, args.account);
+        let change = self.audit(RoleAction::Revoked, args.account, Some(args.role), Some(admin));
+        self.emit(PassportEventV1::RoleChanged { change });
+        Ok(())
+    }
+
+    /// Первый шаг передачи прав: текущий администратор называет преемника.
+    /// Права переходят только после `AcceptAdmin`, поэтому опечатка в адресе
+    /// не оставляет приложение без администратора.
+    fn propose_admin(&mut self, args: ProposeAdminArgs) -> Result<()> {
+        let admin = self.authorized_admin()?;
+        let change = match args.candidate {
+            Some(candidate) => {
+                ensure!(candidate != admin, "candidate is already the admin");
+                self.state.pending_admin.set(Some(candidate));
+                log::info!("Admin {:?} proposed {:?} as new admin", admin, candidate);
+                self.audit(RoleAction::AdminProposed, candidate, None, Some(admin))
+            }
+            None => {
+                let Some(candidate) = *self.state.pending_admin.get() else {
+                    bail!("no pending admin proposal");
+                };
+                self.state.pending_admin.set(None);
+                log::info!("Admin {:?} cancelled proposal for {:?}", admin, candidate);
+                self.audit(RoleAction::AdminProposalCancelled, candidate, None, Some(admin))
+            }
+        };
+        self.emit(PassportEventV1::RoleChanged { change });
+        Ok(())
+    }
+
+    fn accept_admin(&mut self) -> Result<()> {
+        let Some(signer) = self.runtime.authenticated_signer() else {
+            bail!("accepting admin rights requires authentication");
+        };
+        ensure!(
+            self.state.pending_admin.get().as_ref() == Some(&signer),
+            "signer is not the proposed admin"
+        );
+        let previous = *self.state.admin.get();
+        self.state.admin.set(Some(signer));
+        self.state.pending_admin.set(None);
+
+        log::info!("Admin changed from {:?} to {:?}", previous, signer);
+        self.audit(RoleAction::AdminChanged, signer, None, Some(signer));
+        self.emit(PassportEventV1::AdminChanged {
+            previous,
+            admin: Some(signer),
+        });
+        Ok(())
+    }
+
+    /// Счет приложения, на котором хранятся залоги оракулов
+    fn application_account(&mut self) -> Account {
+        Account {
+            chain_id: self.runtime.chain_id(),
+            owner: AccountOwner::from(self.runtime.application_id().forget_abi()),
+        }
+    }
+
+    /// Реестр оракулов ведется только на цепочке-создателе
+    fn ensure_creator_chain(&mut self) -> Result<()> {
+        ensure!(
+            self.runtime.chain_id() == self.runtime.application_creator_chain_id(),
+            "the oracle registry lives on the application creator chain"
+        );
+        Ok(())
+    }
+
+    async fn oracle_record(&self, oracle: AccountOwner) -> Result<OracleRecord> {
+        self.state
+            .oracles
+            .get(&oracle)
+            .await
+            .context("failed to load oracle record")?
+            .context("oracle is not registered")
+    }
+
+    fn store_oracle_record(&mut self, record: OracleRecord) -> Result<()> {
+        self.state
+            .oracles
+            .insert(&record.oracle, record)
+            .context("failed to store oracle record")
+    }
+
+    /// Вносит залог подписанта на счет приложения; первый взнос регистрирует оракула,
+    /// который ждет одобрения через `AddOracle`
+    async fn register_oracle(&mut self, args: RegisterOracleArgs) -> Result<()> {
+        self.ensure_creator_chain()?;
+        let Some(oracle) = self.runtime.authenticated_signer() else {
+            bail!("staking requires authentication");
+        };
+        ensure!(args.stake > Amount::ZERO, "stake must be positive");
+        let now = self.runtime.system_time();
+        let mut record = self
+            .state
+            .oracles
+            .get(&oracle)
+            .await
+            .context("failed to load oracle record")?
+            .unwrap_or(OracleRecord {
+                oracle,
+                registered_at: now,
+                stake: Amount::ZERO,
+                unbonding: None,
+                submissions: 0,
+                disputes_lost: 0,
+                // Залог сам по себе не дает права писать в паспорта
+                status: OracleStatus::Pending,
+                genesis: false,
+            });
+        record.stake = record.stake.try_add(args.stake).context("stake overflow")?;
+        let min_stake = self.runtime.application_parameters().staking.min_stake;
+        ensure!(
+            record.stake >= min_stake,
+            "stake {} is below the minimum of {}",
+            record.stake,
+            min_stake
+        );
+
+        let destination = self.application_account();
+        self.runtime.transfer(oracle, destination, args.stake);
+        let stake = record.stake;
+        self.store_oracle_record(record)?;
+
+        log::info!("Oracle {:?} staked {}, total {}", oracle, args.stake, stake);
+        self.emit(PassportEventV1::OracleStaked { oracle, stake });
+        Ok(())
+    }
+
+    /// Переводит часть залога в вывод; новый вывод продлевает задержку всей суммы
+    async fn unbond_stake(&mut self, args: UnbondStakeArgs) -> Result<()> {
+        self.ensure_creator_chain()?;
+        let Some(oracle) = self.runtime.authenticated_signer() else {
+            bail!("unbonding requires authentication");
+        };
+        let mut record = self.oracle_record(oracle).await?;
+        ensure!(
+            args.amount > Amount::ZERO && args.amount <= record.stake,
+            "unbond amount must be positive and at most the current stake"
+        );
+        let delay = self.runtime.application_parameters().staking.unbonding_delay_secs;
+        let now = self.runtime.system_time();
+
+        record.stake = record.stake.saturating_sub(args.amount);
+        let amount = record
+            .unbonding
+            .as_ref()
+            .map_or(args.amount, |unbonding| unbonding.amount.saturating_add(args.amount));
+        let unbonding = Unbonding {
+            amount,
+            available_at: now.saturating_add(TimeDelta::from_secs(delay)),
+        };
+        record.unbonding = Some(unbonding.clone());
+        self.store_oracle_record(record)?;
+
+        log::info!("Oracle {:?} unbonding {}", oracle, amount);
+        self.emit(PassportEventV1::OracleUnbonding { oracle, unbonding });
+        Ok(())
+    }
+
+    /// Возвращает оракулу залог, задержка вывода которого истекла
+    async fn withdraw_stake(&mut self) -> Result<()> {
+        self.ensure_creator_chain()?;
+        let Some(oracle) = self.runtime.authenticated_signer() else {
+            bail!("withdrawing stake requires authentication");
+        };
+        let mut record = self.oracle_record(oracle).await?;
+        let Some(unbonding) = record.unbonding.take() else {
+            bail!("no stake is unbonding");
+        };
+        ensure!(
+            unbonding.available_at <= self.runtime.system_time(),
+            "unbonding delay has not ended"
+        );
+
+        let source = self.application_account().owner;
+        let destination = Account {
+            chain_id: self.runtime.chain_id(),
+            owner: oracle,
+        };
+        self.runtime.transfer(source, destination, unbonding.amount);
+        self.store_oracle_record(record)?;
+
+        log::info!("Oracle {:?} withdrew {}", oracle, unbonding.amount);
+        self.emit(PassportEventV1::OracleWithdrew {
+            oracle,
+            amount: unbonding.amount,
+        });
+        Ok(())
+    }
+
+    /// Списывает залог оракула: сначала активный, затем выводимый
+    async fn slash_oracle(&mut self, args: SlashOracleArgs) -> Result<()> {
+        self.ensure_creator_chain()?;
+        let by = self.authorized(Role::OracleManager).await?;
+        ensure!(
+            !args.reason.trim().is_empty() && args.reason.len() <= 256,
+            "slash reason must be 1 to 256 characters"
+        );
+        let mut record = self.oracle_record(args.oracle).await?;
+
+        let from_stake = args.amount.min(record.stake);
+        record.stake = record.stake.saturating_sub(from_stake);
+        let mut slashed = from_stake;
+        if let Some(unbonding) = record.unbonding.as_mut() {
+            let from_unbonding = args.amount.saturating_sub(from_stake).min(unbonding.amount);
+            unbonding.amount = unbonding.amount.saturating_sub(from_unbonding);
+            slashed = slashed.saturating_add(from_unbonding);
+        }
+        record.unbonding = record
+            .unbonding
+            .filter(|unbonding| unbonding.amount > Amount::ZERO);
+        ensure!(slashed > Amount::ZERO, "oracle has no stake to slash");
+        let suspended = args.suspend && record.status != OracleStatus::Suspended;
+        if suspended {
+            record.status = OracleStatus::Suspended;
+        }
+        self.store_oracle_record(record)?;
+        let total = self.state.slashed_stake.get_mut();
+        *total = total.saturating_add(slashed);
+
+        log::warn!(
+            "{:?} slashed oracle {:?} by {}: {}",
+            by,
+            args.oracle,
+            slashed,
+            args.reason
+        );
+        if suspended {
+            self.audit(RoleAction::OracleRemoved, args.oracle, None, Some(by));
+            self.emit(PassportEventV1::OracleRemoved {
+                oracle: args.oracle,
+                by,
+            });
+        }
+        self.emit(PassportEventV1::OracleSlashed {
+            oracle: args.oracle,
+            amount: slashed,
+            reason: args.reason,
+            by,
+        });
+        Ok(())
+    }
+
+    /// Учитывает принятую операцию оракула в реестре
+    async fn record_submission(&mut self, oracle: AccountOwner) -> Result<()> {
+        let mut record = self.oracle_record(oracle).await?;
+        record.submissions += 1;
+        self.store_oracle_record(record)
+    }
+
+    /// Учитывает отклоненное после спора обновление; с другой цепочки результат
+    /// отправляется на цепочку-создатель
+    async fn record_dispute_lost(&mut self, oracle: AccountOwner) -> Result<()> {
+        let creator_chain_id = self.runtime.application_creator_chain_id();
+        if self.runtime.chain_id() != creator_chain_id {
+            self.runtime
+                .prepare_message(PassportMessage::DisputeLost { oracle })
+                .send_to(creator_chain_id);
+            return Ok(());
+        }
+        let record = self
+            .state
+            .oracles
+            .get(&oracle)
+            .await
+            .context("failed to load oracle record")?;
+        // Оракул мог полностью выйти из реестра
+        if let Some(mut record) = record {
+            record.disputes_lost += 1;
+            self.store_oracle_record(record)?;
+        }
+        Ok(())
+    }
+
+    async fn add_oracle(&mut self, args: AddOracleArgs) -> Result<()> {
+        // SECURITY FIX: Only admin or oracle managers can approve or reinstate oracles
+        let signer = self.authorized(Role::OracleManager).await?;
+        let mut record = self.oracle_record(args.oracle).await?;
+        ensure!(
+            record.status != OracleStatus::Active,
+            "oracle is already active"
+        );
+        record.status = OracleStatus::Active;
+        self.store_oracle_record(record)?;
+
+        self.audit(RoleAction::OracleAdded, args.oracle, None, Some(signer));
+        self.emit(PassportEventV1::OracleAdded {
+            oracle: args.oracle,
+            by: signer,
+        });
+
+        log::info!("{:?} activated oracle: {:?}", signer, args.oracle);
+        Ok(())
+    }
+
+    async fn remove_oracle(&mut self, args: RemoveOracleArgs) -> Result<()> {
+        // SECURITY FIX: Only admin or oracle managers can suspend oracles
+        let signer = self.authorized(Role::OracleManager).await?;
+        let mut record = self.oracle_record(args.oracle).await?;
+        ensure!(
+            record.status != OracleStatus::Suspended,
+            "oracle is already suspended"
+        );
+        record.status = OracleStatus::Suspended;
+        self.store_oracle_record(record)?;
+
+        self.audit(RoleAction::OracleRemoved, args.oracle, None, Some(signer));
+        self.emit(PassportEventV1::OracleRemoved {
+            oracle: args.oracle,
+            by: signer,
+        });
+
+        log::info!("{:?} suspended oracle: {:?}", signer, args.oracle);
+        Ok(())
+    }
+}
+
+/// Добавляет достижения оракула (один код - одна запись) и прирост скора в паспорт.
+/// Возвращает итог по кодам и события для публикации.
+fn apply_update(
+    token_id: &TokenId,
+    passport: &mut Passport,
+    achievements: Vec<Achievement>,
+    score_increase: u64,
+    limits: &PassportLimits,
+) -> Result<(AchievementUpdateOutcome, Vec<PassportEventV1>)> {
+    let mut outcome = AchievementUpdateOutcome::default();
+    let mut events = Vec::new();
+    for record in achievements {
+        let code = record.code.clone();
+        match passport
+            .achievements
+            .iter_mut()
+            .find(|existing| existing.code == code)
+        {
+            None => {
+                events.push(PassportEventV1::AchievementGranted {
+                    token_id: token_id.clone(),
+                    achievement: record.clone(),
+                    upgraded: false,
+                });
+                passport.achievements.push(record);
+                outcome.granted.push(code);
+            }
+            Some(existing) if record.points > existing.points => {
+                events.push(PassportEventV1::AchievementGranted {
+                    token_id: token_id.clone(),
+                    achievement: record.clone(),
+                    upgraded: true,
+                });
+                *existing = record;
+                outcome.upgraded.push(code);
+            }
+            Some(_) => outcome.ignored.push(code),
+        }
+    }
+
+    // CRITICAL FIX #3: Проверка общего количества достижений
+    ensure!(
+        passport.achievements.len() <= limits.max_achievements as usize,
+        "total achievements limit ({}) exceeded",
+        limits.max_achievements
+    );
+
+    // Увеличить скор
+    if score_increase > 0 {
+        passport.score = passport
+            .score
+            .checked_add(score_increase)
+            .context("score overflow")?;
+    }
+    events.push(score_changed(token_id.clone(), passport));
+    Ok((outcome, events))
+}
+
+/// Итог голоса оракула в режиме кворума
+enum Vote {
+    Pending(ProposalStatus),
+    /// Оракул уже голосовал за это предложение; голос не учтен повторно
+    Repeated(ProposalStatus),
+    /// Кворум набран голосами `approvals`
+    Reached {
+        args: UpdateArgs,
+        approvals: Vec<AccountOwner>,
+    },
+}
+
+/// Совпадает ли обновление с предложением. Хеши доказательств у оракулов
+/// различаются, поэтому сравниваются только коды, очки и прирост скора.
+fn same_update(proposal: &UpdateProposal, args: &UpdateArgs) -> bool {
+    fn normalized(achievements: &[AchievementInput]) -> Vec<(&str, u64)> {
+        let mut entries: Vec<_> = achievements
+            .iter()
+            .map(|achievement| (achievement.code.as_str(), achievement.points))
+            .collect();
+        entries.sort_unstable();
+        entries
+    }
+    proposal.target_chain == args.target_chain
+        && proposal.score_increase == args.score_increase
+        && normalized(&proposal.new_achievements) == normalized(&args.new_achievements)
+}
+
+/// Сообщение об ошибке с кодом `PassportError`, если он есть
+fn failure_message(context: &str, error: &anyhow::Error) -> String {
+    match error.downcast_ref::<PassportError>() {
+        Some(passport_error) => format!("{context} [{}]: {error:#}", passport_error.code()),
+        None => format!("{context}: {error:#}"),
+    }
+}
+
+fn validate_update(args: &UpdateArgs, limits: &PassportLimits) -> Result<()> {
+    // CRITICAL FIX #2: Проверка лимита достижений
+    ensure!(
+        args.new_achievements.len() <= limits.max_achievements_per_update as usize,
+        "too many achievements in single update"
+    );
+
+    for achievement in &args.new_achievements {
+        validate_achievement(&achievement.code, achievement.evidence_hash.as_deref())?;
+    }
+    Ok(())
+}
+
+/// Событие с текущими значениями скора паспорта
+fn score_changed(token_id: TokenId, passport: &Passport) -> PassportEventV1 {
+    PassportEventV1::ScoreChanged {
+        token_id,
+        score: passport.score,
+        self_reported_score: passport.self_reported_score,
+        revision: passport.revision,
+    }
+}
+
+fn validate_uris(
+    metadata_uri: &str,
+    image_uri: &str,
+    content_hash: &str,
+    limits: &PassportLimits,
+) -> Result<()> {
+    let max = limits.max_uri_length as usize;
+    ensure!(
+        metadata_uri.len() <= max,
+        "metadata_uri must be at most {max} characters"
+    );
+    ensure!(
+        image_uri.len() <= max,
+        "image_uri must be at most {max} characters"
+    );
+    ensure!(
+        content_hash.len() <= max,
+        "content_hash must be at most {max} characters"
+    );
+    ensure!(
+        !metadata_uri.is_empty() && !image_uri.is_empty() && !content_hash.is_empty(),
+        "URIs and content hash must be non-empty"
+    );
+    Ok(())
+}
+
+fn validate_achievement(code: &str, evidence_hash: Option<&str>) -> Result<()> {
+    ensure!(
+        !code.is_empty() && code.len() <= 64,
+        "achievement code must be 1 to 64 characters"
+    );
+    ensure!(
+        code.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')),
+        "achievement code may only contain ASCII letters, digits and _ - . :"
+    );
+    if let Some(hash) = evidence_hash {
+        ensure!(
+            !hash.is_empty() && hash.len() <= 128,
+            "evidence_hash must be 1 to 128 characters"
+        );
+    }
     Ok(())
 }
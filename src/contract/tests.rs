@@ -0,0 +1,981 @@
+use std::str::FromStr;
+
+use futures::FutureExt as _;
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ChainId, ChainOwnership, Timestamp},
+    util::BlockingWait,
+    views::View,
+    Contract, ContractRuntime,
+};
+use passport_nft::{
+    error::PassportError, AchievementInput, AchievementUpdateOutcome, AddAchievementArgs, BurnArgs,
+    DisputeArgs, IncreaseScoreArgs, MigrateArgs, MintArgs, MintPolicy, OracleQuorum, OracleRecord,
+    Passport, PassportEvent, PassportEventV1, PassportInstantiationArgument, PassportLimits,
+    PassportMessage, PassportOperation, PassportParameters, PassportResponse, PassportState,
+    PauseFlags, ProposalStatus, ProposeAdminArgs, ResolveDisputeArgs, RevocationReason, RevokeArgs,
+    Role, RoleAction, RoleArgs, SetPauseArgs, TokenId, Tombstone, TombstoneKind, TransferArgs,
+    TransferPolicy, UpdateArgs,
+};
+
+use super::PassportContract;
+
+const CHAIN: &str = "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65";
+const ADMIN: &str = "0x5487b70625ce71f7ee29154ad32aefa1c526cb483bdb783dea2e1d17bc497844";
+const OWNER: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const OTHER_CHAIN: &str = "a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1";
+const ORACLE: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+fn account(owner: &str) -> AccountOwner {
+    AccountOwner::from_str(owner).unwrap()
+}
+
+fn token() -> TokenId {
+    TokenId { id: vec![1] }
+}
+
+/// Контракт с параметрами по умолчанию, созданный `ADMIN` на цепочке `CHAIN`
+fn instantiated() -> PassportContract {
+    instantiated_with(PassportParameters::default())
+}
+
+fn instantiated_with(parameters: PassportParameters) -> PassportContract {
+    instantiated_on(CHAIN, parameters)
+}
+
+/// Контракт на цепочке `chain`; цепочка-создатель всегда `CHAIN`
+fn instantiated_on(chain: &str, parameters: PassportParameters) -> PassportContract {
+    instantiated_with_argument(chain, parameters, PassportInstantiationArgument::default())
+}
+
+fn instantiated_with_argument(
+    chain: &str,
+    parameters: PassportParameters,
+    argument: PassportInstantiationArgument,
+) -> PassportContract {
+    let runtime = ContractRuntime::new()
+        .with_application_parameters(parameters)
+        .with_chain_id(ChainId::from_str(chain).unwrap())
+        .with_application_creator_chain_id(ChainId::from_str(CHAIN).unwrap())
+        .with_authenticated_signer(account(ADMIN))
+        .with_system_time(Timestamp::from(1_000));
+    let mut contract = PassportContract {
+        state: PassportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("failed to read from the mock key-value store"),
+        runtime,
+    };
+    contract
+        .instantiate(argument)
+        .now_or_never()
+        .expect("instantiation should not await");
+    contract
+}
+
+fn execute(
+    contract: &mut PassportContract,
+    signer: &str,
+    operation: PassportOperation,
+) -> anyhow::Result<PassportResponse> {
+    contract.runtime.set_authenticated_signer(account(signer));
+    contract
+        .try_execute_operation(operation)
+        .now_or_never()
+        .expect("operation should not await")
+}
+
+/// Доставляет сообщение с цепочки `origin`, подписанное `signer`
+fn deliver(
+    contract: &mut PassportContract,
+    signer: &str,
+    origin: &str,
+    message: PassportMessage,
+) -> anyhow::Result<()> {
+    contract.runtime.set_authenticated_signer(account(signer));
+    contract
+        .runtime
+        .set_message_origin_chain_id(ChainId::from_str(origin).unwrap());
+    contract.runtime.set_message_is_bouncing(Some(false));
+    contract
+        .try_execute_message(message)
+        .now_or_never()
+        .expect("message should not await")
+}
+
+fn mint_args() -> MintArgs {
+    MintArgs {
+        token_id: token(),
+        metadata_uri: "ipfs://metadata".to_string(),
+        image_uri: "ipfs://image".to_string(),
+        content_hash: "00ff".to_string(),
+        owner: None,
+    }
+}
+
+fn tombstone(contract: &PassportContract, token_id: &TokenId) -> Option<Tombstone> {
+    contract
+        .state
+        .tombstones
+        .get(token_id)
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap()
+}
+
+fn passport(contract: &PassportContract) -> Passport {
+    contract
+        .state
+        .passports
+        .get(&token())
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap()
+        .expect("passport exists")
+}
+
+/// Паспорт `OWNER` и оракул `ORACLE` из аргумента создания приложения
+fn minted_with_oracle() -> PassportContract {
+    minted_with_oracle_and(PassportParameters::default())
+}
+
+fn minted_with_oracle_and(parameters: PassportParameters) -> PassportContract {
+    minted_with_oracles(parameters, &[ORACLE])
+}
+
+/// Начальные оракулы не обязаны вносить залог
+fn minted_with_oracles(parameters: PassportParameters, oracles: &[&str]) -> PassportContract {
+    let argument = PassportInstantiationArgument {
+        admin: None,
+        oracles: oracles.iter().copied().map(account).collect(),
+    };
+    let mut contract = instantiated_with_argument(CHAIN, parameters, argument);
+    execute(&mut contract, OWNER, PassportOperation::Mint(mint_args())).unwrap();
+    contract
+}
+
+fn achievement(code: &str, points: u64) -> AchievementInput {
+    AchievementInput {
+        code: code.to_string(),
+        points,
+        evidence_hash: Some("ab12".to_string()),
+    }
+}
+
+fn update(expected_revision: u64, codes: &[&str], score_increase: u64) -> UpdateArgs {
+    UpdateArgs {
+        token_id: token(),
+        target_chain: None,
+        expected_revision,
+        new_achievements: codes.iter().map(|code| achievement(code, 25)).collect(),
+        score_increase,
+    }
+}
+
+#[test]
+fn oracle_update_records_issuer_and_evidence() {
+    let mut contract = minted_with_oracle();
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+
+    let passport = passport(&contract);
+    let achievement = &passport.achievements[0];
+    assert_eq!(achievement.issuer, account(ORACLE));
+    assert_eq!(achievement.evidence_hash.as_deref(), Some("ab12"));
+    assert_eq!(achievement.awarded_at, Timestamp::from(1_000));
+    assert_eq!(passport.score, 25);
+}
+
+#[test]
+fn owner_claim_is_issued_by_the_owner_without_points() {
+    let mut contract = minted_with_oracle_and(PassportParameters {
+        allow_self_claimed_achievements: true,
+        ..PassportParameters::default()
+    });
+    let claim = AddAchievementArgs {
+        token_id: token(),
+        target_chain: None,
+        code: "SELF_CLAIMED".to_string(),
+        evidence_hash: None,
+    };
+    execute(
+        &mut contract,
+        OWNER,
+        PassportOperation::AddAchievement(claim),
+    )
+    .unwrap();
+
+    let passport = passport(&contract);
+    assert!(passport.achievements.is_empty());
+    let achievement = &passport.self_claimed_achievements[0];
+    assert_eq!(achievement.issuer, account(OWNER));
+    assert_eq!(achievement.points, 0);
+
+    let invalid = AddAchievementArgs {
+        token_id: token(),
+        target_chain: None,
+        code: "has spaces".to_string(),
+        evidence_hash: None,
+    };
+    assert!(execute(
+        &mut contract,
+        OWNER,
+        PassportOperation::AddAchievement(invalid)
+    )
+    .is_err());
+}
+
+#[test]
+fn self_reports_are_off_by_default_and_kept_apart() {
+    let mut contract = minted_with_oracle();
+    let claim = AddAchievementArgs {
+        token_id: token(),
+        target_chain: None,
+        code: "SELF_CLAIMED".to_string(),
+        evidence_hash: None,
+    };
+    assert!(execute(
+        &mut contract,
+        OWNER,
+        PassportOperation::AddAchievement(claim)
+    )
+    .is_err());
+
+    let mut contract = minted_with_oracle_and(PassportParameters {
+        allow_self_reported_score: true,
+        ..PassportParameters::default()
+    });
+    let increase = IncreaseScoreArgs {
+        token_id: token(),
+        target_chain: None,
+        amount: 40,
+    };
+    execute(
+        &mut contract,
+        OWNER,
+        PassportOperation::IncreaseScore(increase),
+    )
+    .unwrap();
+    let passport = passport(&contract);
+    assert_eq!(passport.self_reported_score, 40);
+    assert_eq!(passport.score, 0);
+}
+
+#[test]
+fn repeated_codes_are_upgraded_or_ignored() {
+    let mut contract = minted_with_oracle();
+    let first = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER", "EARLY"], 0));
+    execute(&mut contract, ORACLE, first).unwrap();
+
+    let mut second = update(1, &[], 0);
+    second.new_achievements = vec![
+        achievement("ACTIVE_USER", 50),
+        achievement("EARLY", 10),
+        achievement("WHALE", 5),
+    ];
+    let response = execute(
+        &mut contract,
+        ORACLE,
+        PassportOperation::UpdateAchievements(second),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        PassportResponse::Achievements(AchievementUpdateOutcome {
+            granted: vec!["WHALE".to_string()],
+            upgraded: vec!["ACTIVE_USER".to_string()],
+            ignored: vec!["EARLY".to_string()],
+        })
+    );
+
+    let passport = passport(&contract);
+    assert_eq!(passport.achievements.len(), 3);
+    assert_eq!(passport.achievements[0].points, 50);
+}
+
+#[test]
+fn stale_revision_is_rejected_with_its_code() {
+    let mut contract = minted_with_oracle();
+    let operation = || PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation()).unwrap();
+    assert_eq!(passport(&contract).revision, 1);
+
+    // Повтор того же обновления не применяется второй раз
+    let error = execute(&mut contract, ORACLE, operation()).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<PassportError>(),
+        Some(PassportError::StaleUpdate)
+    ));
+    assert!(super::failure_message("operation failed", &error).contains("[STALE_UPDATE]"));
+    let passport = passport(&contract);
+    assert_eq!(passport.revision, 1);
+    assert_eq!(passport.score, 25);
+}
+
+#[test]
+fn score_change_event_carries_the_new_revision() {
+    let mut contract = minted_with_oracle();
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+
+    let event = super::score_changed(token(), &passport(&contract));
+    assert_eq!(
+        event,
+        PassportEventV1::ScoreChanged {
+            token_id: token(),
+            score: 25,
+            self_reported_score: 0,
+            revision: 1,
+        }
+    );
+
+    // Версия формата - внешний вариант, подписчики различают ее до разбора события
+    let json = serde_json::to_value(PassportEvent::V1(event)).unwrap();
+    assert!(json["V1"]["ScoreChanged"].is_object());
+}
+
+#[test]
+fn updates_for_another_chain_are_forwarded_with_the_signer() {
+    let mut contract = minted_with_oracle();
+    let mut args = update(0, &["ACTIVE_USER"], 25);
+    args.target_chain = Some(ChainId::from_str(OTHER_CHAIN).unwrap());
+    let response = execute(
+        &mut contract,
+        ORACLE,
+        PassportOperation::UpdateAchievements(args),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        PassportResponse::Forwarded(ChainId::from_str(OTHER_CHAIN).unwrap())
+    );
+    assert_eq!(passport(&contract).revision, 0);
+
+    let requests = contract.runtime.created_send_message_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].destination,
+        ChainId::from_str(OTHER_CHAIN).unwrap()
+    );
+    assert!(requests[0].authenticated && requests[0].is_tracking);
+}
+
+#[test]
+fn oracle_messages_are_accepted_only_from_the_creator_chain() {
+    let mut contract = minted_with_oracle();
+    let message = || PassportMessage::OracleUpdate {
+        args: update(0, &["ACTIVE_USER"], 25),
+        approvals: Vec::new(),
+    };
+    assert!(deliver(&mut contract, ORACLE, OTHER_CHAIN, message()).is_err());
+    assert_eq!(passport(&contract).score, 0);
+
+    deliver(&mut contract, ORACLE, CHAIN, message()).unwrap();
+    let passport = passport(&contract);
+    assert_eq!(passport.score, 25);
+    assert_eq!(passport.achievements[0].issuer, account(ORACLE));
+}
+
+#[test]
+fn migration_moves_the_passport_and_a_bounce_restores_it() {
+    let mut contract = minted_with_oracle();
+    let minted = passport(&contract);
+    let other_chain = ChainId::from_str(OTHER_CHAIN).unwrap();
+    let migrate = MigrateArgs {
+        token_id: token(),
+        destination: other_chain,
+    };
+    let response = execute(&mut contract, OWNER, PassportOperation::Migrate(migrate)).unwrap();
+    assert_eq!(response, PassportResponse::Forwarded(other_chain));
+
+    let stored = |contract: &PassportContract| {
+        contract
+            .state
+            .passports
+            .get(&token())
+            .now_or_never()
+            .expect("view read should not await")
+            .unwrap()
+    };
+    assert!(stored(&contract).is_none());
+    let migrated_to = contract
+        .state
+        .migrated_to
+        .get(&token())
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap();
+    assert_eq!(migrated_to, Some(other_chain));
+    assert_eq!(contract.runtime.created_send_message_requests().len(), 1);
+
+    // Цепочка назначения отклонила перенос
+    contract.runtime.set_message_is_bouncing(Some(true));
+    contract
+        .try_execute_message(PassportMessage::MigratePassport {
+            passport: minted.clone(),
+            destination: other_chain,
+        })
+        .now_or_never()
+        .expect("message should not await")
+        .unwrap();
+    let restored = stored(&contract).expect("passport restored");
+    assert_eq!(restored.owner, minted.owner);
+    assert_eq!(restored.owner_chain, ChainId::from_str(CHAIN).unwrap());
+}
+
+#[test]
+fn migrated_passport_is_accepted_only_from_its_owner() {
+    let mut contract = instantiated();
+    contract
+        .runtime
+        .set_chain_ownership(ChainOwnership::single(account(OWNER)));
+    let mut incoming = {
+        let mut source = minted_with_oracle();
+        execute(
+            &mut source,
+            ORACLE,
+            PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25)),
+        )
+        .unwrap();
+        passport(&source)
+    };
+    incoming.owner_chain = ChainId::from_str(OTHER_CHAIN).unwrap();
+
+    let message = PassportMessage::MigratePassport {
+        passport: incoming.clone(),
+        destination: ChainId::from_str(CHAIN).unwrap(),
+    };
+    assert!(deliver(&mut contract, ORACLE, OTHER_CHAIN, message).is_err());
+
+    let message = PassportMessage::MigratePassport {
+        passport: incoming,
+        destination: ChainId::from_str(CHAIN).unwrap(),
+    };
+    deliver(&mut contract, OWNER, OTHER_CHAIN, message).unwrap();
+    let passport = passport(&contract);
+    assert_eq!(passport.owner_chain, ChainId::from_str(CHAIN).unwrap());
+    assert_eq!(passport.score, 25);
+    assert_eq!(passport.revision, 1);
+}
+
+#[test]
+fn burned_token_id_is_never_minted_again() {
+    let mut contract = minted_with_oracle();
+    let burn = BurnArgs {
+        token_id: token(),
+        target_chain: None,
+    };
+    execute(&mut contract, OWNER, PassportOperation::Burn(burn)).unwrap();
+    let tombstone = tombstone(&contract, &token()).expect("burn leaves a tombstone");
+    assert_eq!(tombstone.kind, TombstoneKind::Burned);
+    assert_eq!(tombstone.removed_by, account(OWNER));
+
+    assert!(execute(&mut contract, OWNER, PassportOperation::Mint(mint_args())).is_err());
+
+    // Уничтожение освобождает владельца для нового выпуска
+    let fresh = MintArgs {
+        token_id: TokenId { id: vec![2] },
+        ..mint_args()
+    };
+    execute(&mut contract, OWNER, PassportOperation::Mint(fresh)).unwrap();
+}
+
+#[test]
+fn revoked_owner_cannot_mint_again() {
+    let mut contract = minted_with_oracle();
+    let revoke = || {
+        PassportOperation::Revoke(RevokeArgs {
+            token_id: token(),
+            target_chain: None,
+            reason: RevocationReason::Sybil,
+        })
+    };
+    assert!(execute(&mut contract, OWNER, revoke()).is_err());
+    execute(&mut contract, ADMIN, revoke()).unwrap();
+    let tombstone = tombstone(&contract, &token()).expect("revocation leaves a tombstone");
+    assert_eq!(tombstone.kind, TombstoneKind::Revoked);
+    assert_eq!(tombstone.reason, Some(RevocationReason::Sybil));
+
+    let fresh = MintArgs {
+        token_id: TokenId { id: vec![2] },
+        ..mint_args()
+    };
+    assert!(execute(&mut contract, OWNER, PassportOperation::Mint(fresh)).is_err());
+}
+
+fn transfer(new_owner: &str) -> PassportOperation {
+    PassportOperation::Transfer(TransferArgs {
+        token_id: token(),
+        target_chain: None,
+        new_owner: account(new_owner),
+    })
+}
+
+#[test]
+fn transfer_follows_the_policy() {
+    let mut soulbound = minted_with_oracle();
+    assert!(execute(&mut soulbound, OWNER, transfer(ADMIN)).is_err());
+
+    let mut contract = minted_with_oracle_and(PassportParameters {
+        transfer_policy: TransferPolicy::TransferableWithScoreReset,
+        ..PassportParameters::default()
+    });
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+    assert!(execute(&mut contract, ADMIN, transfer(ADMIN)).is_err());
+
+    let response = execute(&mut contract, OWNER, transfer(ADMIN)).unwrap();
+    assert_eq!(response, PassportResponse::Ok);
+    let passport = passport(&contract);
+    assert_eq!(passport.owner, account(ADMIN));
+    assert_eq!(passport.score, 0);
+    assert!(passport.achievements.is_empty());
+    assert_eq!(passport.revision, 2);
+    assert_eq!(passport.transfers.len(), 1);
+}
+
+#[test]
+fn transfer_off_the_creator_chain_waits_for_its_approval() {
+    let parameters = PassportParameters {
+        transfer_policy: TransferPolicy::Transferable,
+        ..PassportParameters::default()
+    };
+    let mut contract = instantiated_on(OTHER_CHAIN, parameters);
+    deliver(
+        &mut contract,
+        OWNER,
+        CHAIN,
+        PassportMessage::MintApproved(mint_args()),
+    )
+    .unwrap();
+
+    let response = execute(&mut contract, OWNER, transfer(ADMIN)).unwrap();
+    assert_eq!(
+        response,
+        PassportResponse::Forwarded(ChainId::from_str(CHAIN).unwrap())
+    );
+    assert_eq!(passport(&contract).owner, account(OWNER));
+
+    let approved = |new_owner| {
+        PassportMessage::TransferApproved(TransferArgs {
+            token_id: token(),
+            target_chain: None,
+            new_owner: account(new_owner),
+        })
+    };
+    assert!(deliver(&mut contract, OWNER, OTHER_CHAIN, approved(ADMIN)).is_err());
+    deliver(&mut contract, OWNER, CHAIN, approved(ADMIN)).unwrap();
+    assert_eq!(passport(&contract).owner, account(ADMIN));
+}
+
+#[test]
+fn creator_chain_refuses_transfers_to_revoked_owners() {
+    let mut contract = minted_with_oracle();
+    let revoke = RevokeArgs {
+        token_id: token(),
+        target_chain: None,
+        reason: RevocationReason::Abuse,
+    };
+    execute(&mut contract, ADMIN, PassportOperation::Revoke(revoke)).unwrap();
+
+    let request = |new_owner| {
+        PassportMessage::RegisterTransfer(TransferArgs {
+            token_id: TokenId { id: vec![2] },
+            target_chain: None,
+            new_owner: account(new_owner),
+        })
+    };
+    assert!(deliver(&mut contract, ADMIN, OTHER_CHAIN, request(OWNER)).is_err());
+    deliver(&mut contract, ADMIN, OTHER_CHAIN, request(ORACLE)).unwrap();
+    let requests = contract.runtime.created_send_message_requests();
+    let approval = requests.last().expect("approval sent");
+    assert_eq!(
+        approval.destination,
+        ChainId::from_str(OTHER_CHAIN).unwrap()
+    );
+}
+
+#[test]
+fn instantiation_argument_sets_admin_and_oracles() {
+    let argument = PassportInstantiationArgument {
+        admin: Some(account(OWNER)),
+        oracles: vec![account(ORACLE)],
+    };
+    let mut contract = instantiated_with_argument(CHAIN, PassportParameters::default(), argument);
+    assert_eq!(*contract.state.admin.get(), Some(account(OWNER)));
+
+    execute(&mut contract, OWNER, PassportOperation::Mint(mint_args())).unwrap();
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+    assert_eq!(passport(&contract).score, 25);
+}
+
+#[test]
+fn parameters_limit_sizes_and_who_mints() {
+    let mut contract = instantiated_with(PassportParameters {
+        limits: PassportLimits {
+            max_uri_length: 8,
+            ..PassportLimits::default()
+        },
+        ..PassportParameters::default()
+    });
+    // "ipfs://metadata" длиннее 8 символов
+    assert!(execute(&mut contract, OWNER, PassportOperation::Mint(mint_args())).is_err());
+
+    let mut closed = instantiated_with(PassportParameters {
+        mint_policy: MintPolicy::Closed,
+        ..PassportParameters::default()
+    });
+    assert!(execute(&mut closed, OWNER, PassportOperation::Mint(mint_args())).is_err());
+
+    let mut owners_only = instantiated_with(PassportParameters {
+        mint_policy: MintPolicy::ChainOwnersOnly,
+        ..PassportParameters::default()
+    });
+    owners_only
+        .runtime
+        .set_chain_ownership(ChainOwnership::single(account(ADMIN)));
+    assert!(execute(
+        &mut owners_only,
+        OWNER,
+        PassportOperation::Mint(mint_args())
+    )
+    .is_err());
+    execute(
+        &mut owners_only,
+        ADMIN,
+        PassportOperation::Mint(mint_args()),
+    )
+    .unwrap();
+}
+
+#[test]
+fn admin_rights_move_in_two_steps() {
+    let mut contract = instantiated();
+    let propose = || {
+        PassportOperation::ProposeAdmin(ProposeAdminArgs {
+            candidate: Some(account(OWNER)),
+        })
+    };
+    assert!(execute(&mut contract, OWNER, propose()).is_err());
+    execute(&mut contract, ADMIN, propose()).unwrap();
+    assert_eq!(*contract.state.admin.get(), Some(account(ADMIN)));
+
+    assert!(execute(&mut contract, ORACLE, PassportOperation::AcceptAdmin).is_err());
+    execute(&mut contract, OWNER, PassportOperation::AcceptAdmin).unwrap();
+    assert_eq!(*contract.state.admin.get(), Some(account(OWNER)));
+    assert_eq!(*contract.state.pending_admin.get(), None);
+
+    let log = contract
+        .state
+        .role_log
+        .read(..)
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap();
+    let actions: Vec<_> = log.iter().map(|change| change.action).collect();
+    assert_eq!(
+        actions[actions.len() - 2..],
+        [RoleAction::AdminProposed, RoleAction::AdminChanged]
+    );
+}
+
+#[test]
+fn granted_role_allows_its_operations() {
+    let mut contract = minted_with_oracle();
+    let revoke = || {
+        PassportOperation::Revoke(RevokeArgs {
+            token_id: token(),
+            target_chain: None,
+            reason: RevocationReason::Other,
+        })
+    };
+    assert!(execute(&mut contract, ORACLE, revoke()).is_err());
+
+    let grant = || {
+        PassportOperation::GrantRole(RoleArgs {
+            account: account(ORACLE),
+            role: Role::Moderator,
+        })
+    };
+    assert!(execute(&mut contract, ORACLE, grant()).is_err());
+    execute(&mut contract, ADMIN, grant()).unwrap();
+    execute(&mut contract, ORACLE, revoke()).unwrap();
+    let tombstone = tombstone(&contract, &token()).expect("revocation leaves a tombstone");
+    assert_eq!(tombstone.removed_by, account(ORACLE));
+}
+
+#[test]
+fn minting_for_another_account_needs_the_minter_role() {
+    let mut contract = instantiated();
+    let for_owner = || {
+        PassportOperation::Mint(MintArgs {
+            owner: Some(account(OWNER)),
+            ..mint_args()
+        })
+    };
+    assert!(execute(&mut contract, ORACLE, for_owner()).is_err());
+
+    let grant = RoleArgs {
+        account: account(ORACLE),
+        role: Role::Minter,
+    };
+    execute(&mut contract, ADMIN, PassportOperation::GrantRole(grant)).unwrap();
+    execute(&mut contract, ORACLE, for_owner()).unwrap();
+    assert_eq!(passport(&contract).owner, account(OWNER));
+}
+
+fn pause(contract: &mut PassportContract, flags: PauseFlags) {
+    let args = SetPauseArgs {
+        target_chain: None,
+        flags,
+    };
+    execute(contract, ADMIN, PassportOperation::SetPause(args)).unwrap();
+}
+
+fn paused_with(error: anyhow::Error) -> Option<PassportError> {
+    error.downcast::<PassportError>().ok()
+}
+
+#[test]
+fn paused_families_fail_with_their_code() {
+    let mut contract = minted_with_oracle();
+    pause(
+        &mut contract,
+        PauseFlags {
+            oracle_updates: true,
+            ..PauseFlags::default()
+        },
+    );
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    let error = execute(&mut contract, ORACLE, operation).unwrap_err();
+    assert!(matches!(
+        paused_with(error),
+        Some(PassportError::OracleUpdatesPaused)
+    ));
+
+    pause(
+        &mut contract,
+        PauseFlags {
+            mint: true,
+            ..PauseFlags::default()
+        },
+    );
+    let fresh = MintArgs {
+        token_id: TokenId { id: vec![2] },
+        ..mint_args()
+    };
+    let error = execute(&mut contract, ORACLE, PassportOperation::Mint(fresh)).unwrap_err();
+    assert!(matches!(
+        paused_with(error),
+        Some(PassportError::MintPaused)
+    ));
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+}
+
+#[test]
+fn creator_chain_pause_stops_requests_from_other_chains() {
+    let mut contract = minted_with_oracle();
+    pause(
+        &mut contract,
+        PauseFlags {
+            mint: true,
+            owner_mutations: true,
+            oracle_updates: false,
+        },
+    );
+
+    let mint = MintArgs {
+        token_id: TokenId { id: vec![2] },
+        owner: Some(account(ORACLE)),
+        ..mint_args()
+    };
+    let error = deliver(
+        &mut contract,
+        ORACLE,
+        OTHER_CHAIN,
+        PassportMessage::RegisterMint(mint),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        paused_with(error),
+        Some(PassportError::MintPaused)
+    ));
+
+    let transfer = TransferArgs {
+        token_id: TokenId { id: vec![2] },
+        target_chain: None,
+        new_owner: account(ADMIN),
+    };
+    let error = deliver(
+        &mut contract,
+        ORACLE,
+        OTHER_CHAIN,
+        PassportMessage::RegisterTransfer(transfer),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        paused_with(error),
+        Some(PassportError::OwnerMutationsPaused)
+    ));
+
+    // Пауза на этой цепочке останавливает и пересылку операций владельца
+    let burn = BurnArgs {
+        token_id: token(),
+        target_chain: Some(ChainId::from_str(OTHER_CHAIN).unwrap()),
+    };
+    let error = execute(&mut contract, OWNER, PassportOperation::Burn(burn)).unwrap_err();
+    assert!(matches!(
+        paused_with(error),
+        Some(PassportError::OwnerMutationsPaused)
+    ));
+}
+
+const SECOND_ORACLE: &str = "0x3333333333333333333333333333333333333333333333333333333333333333";
+
+/// Паспорт с двумя оракулами и кворумом 2 из 2
+fn minted_with_quorum() -> PassportContract {
+    let parameters = PassportParameters {
+        quorum: OracleQuorum {
+            required: 2,
+            ..OracleQuorum::default()
+        },
+        ..PassportParameters::default()
+    };
+    minted_with_oracles(parameters, &[ORACLE, SECOND_ORACLE])
+}
+
+#[test]
+fn repeated_vote_is_not_counted() {
+    let mut contract = minted_with_quorum();
+    let vote = || PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    let pending = PassportResponse::Proposed(ProposalStatus {
+        approvals: 1,
+        required: 2,
+    });
+    assert_eq!(execute(&mut contract, ORACLE, vote()).unwrap(), pending);
+    assert_eq!(execute(&mut contract, ORACLE, vote()).unwrap(), pending);
+
+    let proposals = contract
+        .state
+        .proposals
+        .get(&token())
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap()
+        .expect("proposal stored");
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].approvals, vec![account(ORACLE)]);
+    assert_eq!(passport(&contract).revision, 0);
+}
+
+#[test]
+fn reached_quorum_records_every_voter() {
+    let mut contract = minted_with_quorum();
+    let vote = || PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, vote()).unwrap();
+    execute(&mut contract, SECOND_ORACLE, vote()).unwrap();
+
+    let passport = passport(&contract);
+    assert_eq!(passport.score, 25);
+    let achievement = &passport.achievements[0];
+    assert_eq!(achievement.issuer, account(SECOND_ORACLE));
+    assert_eq!(
+        achievement.approved_by,
+        vec![account(ORACLE), account(SECOND_ORACLE)]
+    );
+}
+
+#[test]
+fn rejected_update_leaves_the_passport_unchanged() {
+    let mut contract = minted_with_oracle_and(PassportParameters {
+        challenge_period_secs: 60,
+        ..PassportParameters::default()
+    });
+    let operation = PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, operation).unwrap();
+    let pending = passport(&contract);
+    assert_eq!((pending.score, pending.revision), (0, 1));
+    assert!(pending.pending_update.is_some());
+
+    // Пока первое обновление на проверке, следующее отклоняется с кодом
+    let operation = PassportOperation::UpdateAchievements(update(1, &["EARLY"], 10));
+    let error = execute(&mut contract, ORACLE, operation).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<PassportError>(),
+        Some(PassportError::UnderReview)
+    ));
+
+    let dispute = DisputeArgs {
+        token_id: token(),
+        target_chain: None,
+        reason: "no such activity".to_string(),
+    };
+    execute(
+        &mut contract,
+        OWNER,
+        PassportOperation::DisputeUpdate(dispute),
+    )
+    .unwrap();
+    let resolve = ResolveDisputeArgs {
+        token_id: token(),
+        target_chain: None,
+        accept: false,
+    };
+    execute(
+        &mut contract,
+        ADMIN,
+        PassportOperation::ResolveDispute(resolve),
+    )
+    .unwrap();
+
+    let rejected = passport(&contract);
+    assert!(rejected.pending_update.is_none());
+    assert!(rejected.achievements.is_empty());
+    assert_eq!((rejected.score, rejected.revision), (0, 1));
+}
+
+fn oracle_record(contract: &PassportContract, oracle: &str) -> OracleRecord {
+    contract
+        .state
+        .oracles
+        .get(&account(oracle))
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap()
+        .expect("oracle registered")
+}
+
+#[test]
+fn genesis_oracles_are_audited_with_the_signer() {
+    let argument = PassportInstantiationArgument {
+        admin: Some(account(OWNER)),
+        oracles: vec![account(ORACLE)],
+    };
+    let contract = instantiated_with_argument(CHAIN, PassportParameters::default(), argument);
+    assert!(oracle_record(&contract, ORACLE).genesis);
+
+    let log = contract
+        .state
+        .role_log
+        .read(..)
+        .now_or_never()
+        .expect("view read should not await")
+        .unwrap();
+    let added = log.last().expect("oracle addition audited");
+    assert_eq!(added.action, RoleAction::OracleAdded);
+    assert_eq!(added.account, account(ORACLE));
+    assert_eq!(added.by, Some(account(ADMIN)));
+}
+
+#[test]
+fn only_the_first_vote_counts_as_a_submission() {
+    let mut contract = minted_with_quorum();
+    let vote = || PassportOperation::UpdateAchievements(update(0, &["ACTIVE_USER"], 25));
+    execute(&mut contract, ORACLE, vote()).unwrap();
+    execute(&mut contract, ORACLE, vote()).unwrap();
+    assert_eq!(oracle_record(&contract, ORACLE).submissions, 1);
+
+    execute(&mut contract, SECOND_ORACLE, vote()).unwrap();
+    assert_eq!(oracle_record(&contract, SECOND_ORACLE).submissions, 1);
+    assert_eq!(passport(&contract).score, 25);
+}
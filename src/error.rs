@@ -1,4 +1,4 @@
-use async_graphql::{Error, ErrorExtensions, Result};
+use async_graphql::{Error, ErrorExtensions};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -29,13 +29,21 @@ pub enum PassportError {
     ContentHashTooLong,
     #[error("passport not found")]
     PassportNotFound,
+    #[error("stale update")]
+    StaleUpdate,
+    #[error("minting is paused")]
+    MintPaused,
+    #[error("owner operations are paused")]
+    OwnerMutationsPaused,
+    #[error("oracle updates are paused")]
+    OracleUpdatesPaused,
+    #[error("passport has an update under review")]
+    UnderReview,
 }
 
 impl ErrorExtensions for PassportError {
-    fn extend(&self) -> Result<Error> {
-        let mut error = Error::new(self.to_string());
-        let error = error.extend_with(|_, e| e.set("code", self.code()));
-        Ok(error)
+    fn extend(&self) -> Error {
+        Error::new(self.to_string()).extend_with(|_, e| e.set("code", self.code()))
     }
 }
 
@@ -55,6 +63,11 @@ impl PassportError {
             PassportError::ImageTooLong => "IMAGE_TOO_LONG",
             PassportError::ContentHashTooLong => "CONTENT_HASH_TOO_LONG",
             PassportError::PassportNotFound => "PASSPORT_NOT_FOUND",
+            PassportError::StaleUpdate => "STALE_UPDATE",
+            PassportError::MintPaused => "MINT_PAUSED",
+            PassportError::OwnerMutationsPaused => "OWNER_MUTATIONS_PAUSED",
+            PassportError::OracleUpdatesPaused => "ORACLE_UPDATES_PAUSED",
+            PassportError::UnderReview => "UPDATE_UNDER_REVIEW",
         }
     }
 }
@@ -1,9 +1,10 @@
+pub mod error;
 pub mod state;
 pub use state::PassportState;
 
-use async_graphql::{InputObject, Request, Response, SimpleObject};
-use linera_base::data_types::Timestamp;
-use linera_base::identifiers::{AccountOwner, ChainId};
+use async_graphql::{ComplexObject, Enum, InputObject, Request, Response, SimpleObject};
+use linera_base::data_types::{Amount, Timestamp};
+use linera_base::identifiers::{AccountOwner, ChainId, StreamName};
 use linera_sdk::abi::{ContractAbi, ServiceAbi};
 use serde::{Deserialize, Serialize};
 
@@ -12,7 +13,7 @@ pub struct PassportNftAbi;
 
 impl ContractAbi for PassportNftAbi {
     type Operation = PassportOperation;
-    type Response = ();
+    type Response = PassportResponse;
 }
 
 impl ServiceAbi for PassportNftAbi {
@@ -20,6 +21,322 @@ impl ServiceAbi for PassportNftAbi {
     type QueryResponse = Response;
 }
 
+/// Параметры приложения, одинаковые на всех цепочках
+///
+/// Самостоятельные заявления владельца хранятся отдельно от данных оракула и
+/// по умолчанию запрещены.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, SimpleObject)]
+pub struct PassportParameters {
+    /// Разрешить владельцу операцию `AddAchievement`
+    #[serde(default)]
+    pub allow_self_claimed_achievements: bool,
+    /// Разрешить владельцу операцию `IncreaseScore`
+    #[serde(default)]
+    pub allow_self_reported_score: bool,
+    /// Можно ли передавать паспорт другому владельцу
+    #[serde(default)]
+    pub transfer_policy: TransferPolicy,
+    /// Кто может выпускать паспорта
+    #[serde(default)]
+    pub mint_policy: MintPolicy,
+    #[serde(default)]
+    pub limits: PassportLimits,
+    /// Сколько оракулов должны согласиться с обновлением
+    #[serde(default)]
+    pub quorum: OracleQuorum,
+    /// Окно оспаривания обновлений оракула в секундах; 0 - обновления
+    /// применяются сразу
+    #[serde(default)]
+    pub challenge_period_secs: u64,
+    /// Условия участия оракулов
+    #[serde(default)]
+    pub staking: OracleStaking,
+}
+
+impl PassportParameters {
+    /// Проверяет согласованность параметров при создании приложения
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.limits.validate()?;
+        self.quorum.validate()?;
+        anyhow::ensure!(
+            self.quorum.required == 1 || self.challenge_period_secs == 0,
+            "choose either an oracle quorum or a challenge period"
+        );
+        self.staking.validate()
+    }
+}
+
+/// Политика выпуска паспортов
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum MintPolicy {
+    /// Любой подписант на любой цепочке
+    #[default]
+    Open,
+    /// Только владелец цепочки, на которой выпускается паспорт
+    ChainOwnersOnly,
+    /// Выпуск новых паспортов закрыт
+    Closed,
+    /// Только аккаунты с ролью `Minter`; роли ведутся на цепочке-создателе,
+    /// поэтому паспорта выпускаются там
+    MintersOnly,
+}
+
+/// Ограничения размеров данных паспорта
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+#[serde(default)]
+pub struct PassportLimits {
+    /// Максимальная длина `metadata_uri`, `image_uri` и `content_hash`
+    pub max_uri_length: u32,
+    /// Максимум достижений в одном обновлении оракула
+    pub max_achievements_per_update: u32,
+    /// Максимум достижений в паспорте (отдельно для проверенных и заявленных)
+    pub max_achievements: u32,
+}
+
+impl Default for PassportLimits {
+    fn default() -> Self {
+        Self {
+            max_uri_length: 256,
+            max_achievements_per_update: 100,
+            max_achievements: 500,
+        }
+    }
+}
+
+impl PassportLimits {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.max_uri_length > 0, "max_uri_length must be positive");
+        anyhow::ensure!(
+            self.max_achievements_per_update > 0,
+            "max_achievements_per_update must be positive"
+        );
+        anyhow::ensure!(
+            self.max_achievements_per_update <= self.max_achievements,
+            "max_achievements_per_update must not exceed max_achievements"
+        );
+        Ok(())
+    }
+}
+
+/// Приостановленные семейства операций. Флаги хранятся на каждой цепочке
+/// отдельно и меняются только администратором.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "PauseFlagsInput")]
+pub struct PauseFlags {
+    /// `Mint`
+    pub mint: bool,
+    /// Операции владельца: самостоятельные заявления, передача, перенос, уничтожение
+    pub owner_mutations: bool,
+    /// `UpdateAchievements` и пересланные обновления оракулов
+    pub oracle_updates: bool,
+}
+
+/// SetPause аргументы
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct SetPauseArgs {
+    /// Цепочка, на которой меняются флаги, если она не совпадает с цепочкой администратора
+    pub target_chain: Option<ChainId>,
+    pub flags: PauseFlags,
+}
+
+/// Роль в приложении. Администратор (`PassportState::admin`) обладает всеми ролями.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum Role {
+    /// Добавляет и удаляет оракулов
+    OracleManager,
+    /// Выпускает паспорта для других аккаунтов
+    Minter,
+    /// Отзывает паспорта
+    Moderator,
+}
+
+/// Вид изменения в журнале ролей
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum RoleAction {
+    Granted,
+    Revoked,
+    OracleAdded,
+    OracleRemoved,
+    AdminProposed,
+    AdminProposalCancelled,
+    AdminChanged,
+}
+
+/// Запись журнала изменений ролей
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct RoleChange {
+    pub action: RoleAction,
+    /// Аккаунт, чьи права изменились
+    pub account: AccountOwner,
+    /// Только для `Granted` и `Revoked`
+    pub role: Option<Role>,
+    /// Подписант изменения; пусто, если приложение создано без подписи
+    pub by: Option<AccountOwner>,
+    pub at: Timestamp,
+}
+
+/// Кворум оракулов (M из N авторизованных) для `UpdateAchievements`
+///
+/// При `required = 1` обновление применяется сразу, как раньше. Иначе каждое
+/// обновление становится предложением для пары паспорт/ревизия и применяется,
+/// когда его одинаково подали `required` разных авторизованных оракулов.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+#[serde(default)]
+pub struct OracleQuorum {
+    pub required: u32,
+    /// Через сколько секунд непринятое предложение истекает
+    pub proposal_ttl_secs: u64,
+}
+
+impl Default for OracleQuorum {
+    fn default() -> Self {
+        Self {
+            required: 1,
+            proposal_ttl_secs: 24 * 60 * 60,
+        }
+    }
+}
+
+impl OracleQuorum {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.required > 0, "quorum.required must be positive");
+        anyhow::ensure!(
+            self.proposal_ttl_secs > 0,
+            "quorum.proposal_ttl_secs must be positive"
+        );
+        Ok(())
+    }
+}
+
+/// Предложение обновления паспорта на ревизии `revision`, ожидающее кворума.
+/// Ревизия фиксирует исходный скор, поэтому совпадение `score_increase`
+/// означает согласие об итоговом скоре.
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct UpdateProposal {
+    pub token_id: TokenId,
+    pub revision: u64,
+    pub target_chain: Option<ChainId>,
+    pub new_achievements: Vec<AchievementInput>,
+    pub score_increase: u64,
+    /// Оракулы, подавшие именно это предложение
+    pub approvals: Vec<AccountOwner>,
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// Голоса за предложение, еще не набравшее кворум
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalStatus {
+    pub approvals: u32,
+    pub required: u32,
+}
+
+/// Залог оракулов в нативном токене
+///
+/// Оракулом становится одобренный менеджером аккаунт, внесший не меньше
+/// `min_stake`; выведенный залог возвращается только через
+/// `unbonding_delay_secs`, чтобы его можно было списать за нарушения,
+/// замеченные позже.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+#[serde(default)]
+pub struct OracleStaking {
+    pub min_stake: Amount,
+    pub unbonding_delay_secs: u64,
+}
+
+impl Default for OracleStaking {
+    fn default() -> Self {
+        Self {
+            min_stake: Amount::from_tokens(100),
+            unbonding_delay_secs: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl OracleStaking {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        // Без залога регистрация открыла бы обновления паспортов кому угодно
+        anyhow::ensure!(self.min_stake > Amount::ZERO, "staking.min_stake must be positive");
+        Ok(())
+    }
+}
+
+/// Состояние оракула в реестре
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum OracleStatus {
+    /// Внес залог и ждет одобрения менеджера оракулов
+    Pending,
+    Active,
+    /// Отстранен менеджером оракулов; залог остается в приложении
+    Suspended,
+}
+
+/// Залог, выводимый после задержки
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct Unbonding {
+    pub amount: Amount,
+    pub available_at: Timestamp,
+}
+
+/// Запись реестра оракулов (на цепочке-создателе)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct OracleRecord {
+    pub oracle: AccountOwner,
+    pub registered_at: Timestamp,
+    /// Залог, дающий право обновлять паспорта
+    pub stake: Amount,
+    pub unbonding: Option<Unbonding>,
+    /// Принятые операции `UpdateAchievements`; в кворуме учитывается только
+    /// первый голос оракула за предложение
+    pub submissions: u64,
+    /// Обновления, отклоненные администратором после оспаривания
+    pub disputes_lost: u64,
+    pub status: OracleStatus,
+    /// Назначен аргументом создания приложения и не обязан держать `min_stake`
+    pub genesis: bool,
+}
+
+impl OracleRecord {
+    /// Может ли оракул сейчас обновлять паспорта
+    pub fn is_eligible(&self, staking: &OracleStaking) -> bool {
+        self.status == OracleStatus::Active && (self.genesis || self.stake >= staking.min_stake)
+    }
+}
+
+/// Аргумент создания приложения; действует только на цепочке-создателе,
+/// где ведутся администратор и реестр оракулов
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassportInstantiationArgument {
+    /// Администратор; по умолчанию - подписант создания приложения
+    #[serde(default)]
+    pub admin: Option<AccountOwner>,
+    /// Оракулы, одобренные с самого начала; заносятся в реестр без залога
+    #[serde(default)]
+    pub oracles: Vec<AccountOwner>,
+}
+
+/// Политика передачи паспорта
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum TransferPolicy {
+    /// Паспорт навсегда привязан к владельцу
+    #[default]
+    Soulbound,
+    /// Паспорт передается вместе с репутацией
+    Transferable,
+    /// При передаче скор, достижения и самостоятельные заявления обнуляются
+    TransferableWithScoreReset,
+}
+
+/// Запись о смене владельца паспорта
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct OwnershipTransfer {
+    pub from: AccountOwner,
+    pub to: AccountOwner,
+    pub at: Timestamp,
+    /// Репутация была обнулена при передаче
+    pub score_reset: bool,
+}
+
 /// Идентификатор токена
 #[derive(
     Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, SimpleObject, InputObject,
@@ -29,8 +346,57 @@ pub struct TokenId {
     pub id: Vec<u8>,
 }
 
+/// Достижение, записанное в паспорт
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, SimpleObject)]
+pub struct Achievement {
+    /// Машиночитаемый код, например `MILESTONE_100`
+    pub code: String,
+    pub points: u64,
+    pub awarded_at: Timestamp,
+    /// Аккаунт, подписавший операцию, которая выдала достижение; при кворуме -
+    /// оракул, чей голос его набрал
+    pub issuer: AccountOwner,
+    /// Все оракулы, проголосовавшие за обновление при кворуме; пусто, если
+    /// достижение выдано одной подписью
+    pub approved_by: Vec<AccountOwner>,
+    /// Хэш данных, на основании которых выдано достижение (hex)
+    pub evidence_hash: Option<String>,
+}
+
+/// Обновление оракула, ожидающее конца окна оспаривания
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, SimpleObject)]
+pub struct PendingUpdate {
+    pub achievements: Vec<Achievement>,
+    pub score_increase: u64,
+    /// Оракул, подавший обновление
+    pub issuer: AccountOwner,
+    pub submitted_at: Timestamp,
+    /// После этого момента неоспоренное обновление можно применить (`FinalizeUpdate`)
+    pub challenge_ends_at: Timestamp,
+    /// Оспоренное обновление ждет решения администратора
+    pub dispute: Option<Dispute>,
+}
+
+/// Оспаривание обновления оракула
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, SimpleObject)]
+pub struct Dispute {
+    pub by: AccountOwner,
+    pub reason: String,
+    pub at: Timestamp,
+}
+
+/// Достижение, передаваемое оракулом; время и выдавший заполняет контракт
+#[derive(Debug, Serialize, Deserialize, Clone, InputObject, SimpleObject)]
+#[graphql(name = "ProposedAchievement", input_name = "AchievementInput")]
+pub struct AchievementInput {
+    pub code: String,
+    pub points: u64,
+    pub evidence_hash: Option<String>,
+}
+
 /// Основная структура паспорта
 #[derive(Debug, Serialize, Deserialize, Clone, SimpleObject)]
+#[graphql(complex)]
 pub struct Passport {
     pub token_id: TokenId,
     pub owner: AccountOwner,
@@ -43,8 +409,42 @@ pub struct Passport {
     pub image_uri: String,
     /// Контрольная сумма off-chain контента (например, SHA-256 hex)
     pub content_hash: String,
-    pub achievements: Vec<String>,
+    /// Достижения, выданные авторизованными оракулами
+    pub achievements: Vec<Achievement>,
+    /// Скор, начисленный только оракулами
     pub score: u64,
+    /// Достижения, добавленные самим владельцем (не проверены)
+    pub self_claimed_achievements: Vec<Achievement>,
+    /// Очки, добавленные самим владельцем (не проверены)
+    pub self_reported_score: u64,
+    /// Номер версии проверенных данных; увеличивается при каждом обновлении оракулом
+    /// и при передаче паспорта
+    pub revision: u64,
+    /// Предыдущие смены владельца, от старых к новым
+    pub transfers: Vec<OwnershipTransfer>,
+    /// Обновление оракула на проверке; `achievements` и `score` его еще не включают
+    pub pending_update: Option<PendingUpdate>,
+}
+
+#[ComplexObject]
+impl Passport {
+    /// Достижения с указанным кодом
+    async fn achievements_by_code(&self, code: String) -> Vec<Achievement> {
+        self.achievements
+            .iter()
+            .filter(|achievement| achievement.code == code)
+            .cloned()
+            .collect()
+    }
+
+    /// Скор с учетом обновления на проверке ("скор на рассмотрении")
+    async fn pending_score(&self) -> u64 {
+        self.pending_update
+            .as_ref()
+            .map_or(self.score, |update| {
+                self.score.saturating_add(update.score_increase)
+            })
+    }
 }
 
 /// Mint аргументы
@@ -57,49 +457,422 @@ pub struct MintArgs {
     pub image_uri: String,
     /// Контрольная сумма off-chain контента (например, SHA-256 hex)
     pub content_hash: String,
+    /// Владелец, если минтер выпускает паспорт для другого аккаунта
+    pub owner: Option<AccountOwner>,
 }
 
 /// AddAchievement аргументы
 #[derive(Debug, Serialize, Deserialize, InputObject)]
 pub struct AddAchievementArgs {
     pub token_id: TokenId,
-    pub achievement: String,
+    /// Цепочка паспорта, если операция выполняется на другой цепочке
+    pub target_chain: Option<ChainId>,
+    pub code: String,
+    pub evidence_hash: Option<String>,
 }
 
 /// IncreaseScore аргументы
 #[derive(Debug, Serialize, Deserialize, InputObject)]
 pub struct IncreaseScoreArgs {
     pub token_id: TokenId,
+    /// Цепочка паспорта, если операция выполняется на другой цепочке
+    pub target_chain: Option<ChainId>,
     pub amount: u64,
 }
 
 /// UpdateAchievements аргументы - используется оракулом для batch обновления
-#[derive(Debug, Serialize, Deserialize, InputObject)]
+#[derive(Debug, Clone, Serialize, Deserialize, InputObject)]
 pub struct UpdateArgs {
     pub token_id: TokenId,
-    pub new_achievements: Vec<String>,
+    /// Цепочка паспорта; если она отличается от цепочки оракула, обновление
+    /// пересылается туда сообщением
+    pub target_chain: Option<ChainId>,
+    /// Ревизия паспорта, на основе которой посчитано обновление; при несовпадении
+    /// операция отклоняется, поэтому повтор или дубликат не применится дважды
+    pub expected_revision: u64,
+    pub new_achievements: Vec<AchievementInput>,
     pub score_increase: u64,
 }
 
-/// AddOracle аргументы - добавить авторизованный оракул
+/// AddOracle аргументы - одобрить нового или вернуть отстраненного оракула
 #[derive(Debug, Serialize, Deserialize, InputObject)]
 pub struct AddOracleArgs {
     pub oracle: AccountOwner,
 }
 
-/// RemoveOracle аргументы - удалить авторизованный оракул
+/// RemoveOracle аргументы - отстранить оракула, не трогая его залог
 #[derive(Debug, Serialize, Deserialize, InputObject)]
 pub struct RemoveOracleArgs {
     pub oracle: AccountOwner,
 }
 
+/// Результат операции контракта
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PassportResponse {
+    #[default]
+    Ok,
+    /// Ответ на `UpdateAchievements`
+    Achievements(AchievementUpdateOutcome),
+    /// Операция отправлена сообщением на цепочку паспорта
+    Forwarded(ChainId),
+    /// Голос оракула учтен, кворум еще не набран
+    Proposed(ProposalStatus),
+}
+
+/// Сообщения между цепочками приложения
+///
+/// Подпись отправителя пересылается вместе с сообщением, поэтому на цепочке
+/// паспорта применяются те же проверки владельца, что и для операций.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PassportMessage {
+    /// Обновление оракула; принимается только от цепочки-создателя приложения,
+    /// где хранится список оракулов. `approvals` - оракулы, набравшие кворум.
+    OracleUpdate {
+        args: UpdateArgs,
+        approvals: Vec<AccountOwner>,
+    },
+    AddAchievement(AddAchievementArgs),
+    IncreaseScore(IncreaseScoreArgs),
+    /// Паспорт целиком переносится на цепочку `destination` через цепочку-создатель,
+    /// которая проверяет его по реестру удаленных паспортов. На исходной цепочке
+    /// он уже удален; при отказе сообщение возвращается и паспорт
+    /// восстанавливается там.
+    MigratePassport {
+        passport: Passport,
+        destination: ChainId,
+    },
+    /// Отзыв паспорта администратором; принимается только от цепочки-создателя
+    Revoke(RevokeArgs),
+    Transfer(TransferArgs),
+    /// Запрос передачи с цепочки паспорта; цепочка-создатель проверяет, не
+    /// отозван ли паспорт у нового владельца
+    RegisterTransfer(TransferArgs),
+    /// Одобренная передача; принимается только от цепочки-создателя
+    TransferApproved(TransferArgs),
+    /// Смена флагов паузы администратором; принимается только от цепочки-создателя
+    SetPause(SetPauseArgs),
+    /// `reviewer` - подписант проверен на цепочке-создателе как администратор или оракул
+    DisputeUpdate { args: DisputeArgs, reviewer: bool },
+    /// Решение администратора; принимается только от цепочки-создателя
+    ResolveDispute(ResolveDisputeArgs),
+    FinalizeUpdate(FinalizeUpdateArgs),
+    /// Администратор отклонил обновление оракула; учитывается в реестре на
+    /// цепочке-создателе
+    DisputeLost { oracle: AccountOwner },
+    /// Запрос выпуска с другой цепочки; цепочка-создатель проверяет роль минтера,
+    /// удаленные паспорта и отозванных владельцев
+    RegisterMint(MintArgs),
+    /// Одобренный выпуск; принимается только от цепочки-создателя
+    MintApproved(MintArgs),
+    /// Цепочка назначения отклонила перенос; цепочка-создатель возвращает паспорт
+    /// на исходную цепочку
+    ReturnMigrated(Passport),
+    /// Цепочка паспорта сообщает цепочке-создателю об уничтожении или отзыве
+    RecordTombstone {
+        token_id: TokenId,
+        tombstone: Tombstone,
+    },
+    Burn(BurnArgs),
+}
+
+/// Что произошло с каждым кодом из `UpdateArgs::new_achievements`
+///
+/// Достижение с одним кодом хранится один раз. Повторная выдача с большим
+/// количеством очков заменяет запись (повышение уровня), иначе игнорируется.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct AchievementUpdateOutcome {
+    /// Коды, выданные впервые
+    pub granted: Vec<String>,
+    /// Коды, у которых выросло количество очков
+    pub upgraded: Vec<String>,
+    /// Коды, которые уже были выданы с тем же или большим количеством очков
+    pub ignored: Vec<String>,
+}
+
+/// Имя потока, в который контракт публикует `PassportEvent`
+pub const PASSPORT_EVENT_STREAM: &[u8] = b"passport_events";
+
+pub fn passport_event_stream() -> StreamName {
+    StreamName(PASSPORT_EVENT_STREAM.to_vec())
+}
+
+/// Событие жизненного цикла паспорта, публикуемое в `PASSPORT_EVENT_STREAM`
+///
+/// Внешний enum - номер версии формата. Существующие варианты не меняются: новые
+/// виды событий добавляются в конец, а несовместимые изменения - новой версией.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PassportEvent {
+    V1(PassportEventV1),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PassportEventV1 {
+    Minted {
+        token_id: TokenId,
+        owner: AccountOwner,
+        owner_chain: ChainId,
+    },
+    /// Достижение выдано оракулом или повышено (`upgraded`)
+    AchievementGranted {
+        token_id: TokenId,
+        achievement: Achievement,
+        upgraded: bool,
+    },
+    /// Владелец сам добавил непроверенное достижение
+    AchievementSelfClaimed {
+        token_id: TokenId,
+        achievement: Achievement,
+    },
+    ScoreChanged {
+        token_id: TokenId,
+        score: u64,
+        self_reported_score: u64,
+        revision: u64,
+    },
+    OracleAdded {
+        oracle: AccountOwner,
+        by: AccountOwner,
+    },
+    OracleRemoved {
+        oracle: AccountOwner,
+        by: AccountOwner,
+    },
+    AdminChanged {
+        previous: Option<AccountOwner>,
+        admin: Option<AccountOwner>,
+    },
+    /// Паспорт принят цепочкой `to` после переноса с цепочки `from`
+    Migrated {
+        token_id: TokenId,
+        owner: AccountOwner,
+        from: ChainId,
+        to: ChainId,
+    },
+    /// Паспорт передан другому владельцу
+    Transferred {
+        token_id: TokenId,
+        from: AccountOwner,
+        to: AccountOwner,
+        score_reset: bool,
+        revision: u64,
+    },
+    /// Паспорт уничтожен владельцем или отозван
+    Removed {
+        token_id: TokenId,
+        tombstone: Tombstone,
+    },
+    /// Роль выдана или отозвана, или предложена смена администратора
+    RoleChanged {
+        change: RoleChange,
+    },
+    /// Оракул подал или поддержал предложение обновления
+    UpdateProposed {
+        token_id: TokenId,
+        revision: u64,
+        oracle: AccountOwner,
+        approvals: u32,
+        required: u32,
+    },
+    /// Обновление оракула записано и ждет конца окна оспаривания
+    UpdatePending {
+        token_id: TokenId,
+        update: PendingUpdate,
+        revision: u64,
+    },
+    UpdateDisputed {
+        token_id: TokenId,
+        dispute: Dispute,
+    },
+    /// Обновление на проверке применено (`accepted`) или отброшено; `by` пусто,
+    /// если оно применено по истечении окна
+    UpdateSettled {
+        token_id: TokenId,
+        accepted: bool,
+        by: Option<AccountOwner>,
+    },
+    /// Оракул внес или пополнил залог
+    OracleStaked {
+        oracle: AccountOwner,
+        stake: Amount,
+    },
+    OracleUnbonding {
+        oracle: AccountOwner,
+        unbonding: Unbonding,
+    },
+    OracleWithdrew {
+        oracle: AccountOwner,
+        amount: Amount,
+    },
+    OracleSlashed {
+        oracle: AccountOwner,
+        amount: Amount,
+        reason: String,
+        by: AccountOwner,
+    },
+    /// Флаги паузы на цепочке изменены
+    PauseChanged {
+        flags: PauseFlags,
+        by: AccountOwner,
+    },
+}
+
+/// Причина отзыва паспорта модерацией
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum RevocationReason {
+    /// Один человек держит несколько паспортов
+    Sybil,
+    /// Злоупотребление или мошенничество
+    Abuse,
+    /// Паспорт выпущен от чужого имени
+    Impersonation,
+    Other,
+}
+
+/// Как паспорт перестал существовать
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum TombstoneKind {
+    Burned,
+    Revoked,
+}
+
+/// Запись об удаленном паспорте; `token_id` с такой записью нельзя выпустить снова,
+/// а владельцу отозванного паспорта закрыт новый выпуск
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct Tombstone {
+    pub owner: AccountOwner,
+    pub kind: TombstoneKind,
+    /// Только для `Revoked`
+    pub reason: Option<RevocationReason>,
+    /// Кто удалил паспорт: владелец или администратор
+    pub removed_by: AccountOwner,
+    pub removed_at: Timestamp,
+}
+
+/// Burn аргументы - владелец уничтожает свой паспорт
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct BurnArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с текущей
+    pub target_chain: Option<ChainId>,
+}
+
+/// Revoke аргументы - администратор отзывает паспорт
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct RevokeArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с цепочкой администратора
+    pub target_chain: Option<ChainId>,
+    pub reason: RevocationReason,
+}
+
+/// DisputeUpdate аргументы - оспорить обновление оракула на проверке
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct DisputeArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с текущей
+    pub target_chain: Option<ChainId>,
+    pub reason: String,
+}
+
+/// ResolveDispute аргументы - решение администратора по оспоренному обновлению
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct ResolveDisputeArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с цепочкой администратора
+    pub target_chain: Option<ChainId>,
+    /// `true` - применить обновление, `false` - отбросить
+    pub accept: bool,
+}
+
+/// FinalizeUpdate аргументы - применить обновление после окна оспаривания
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct FinalizeUpdateArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с текущей
+    pub target_chain: Option<ChainId>,
+}
+
+/// RegisterOracle аргументы - внести залог и стать оракулом (или пополнить залог)
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct RegisterOracleArgs {
+    pub stake: Amount,
+}
+
+/// UnbondStake аргументы - начать вывод части залога
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct UnbondStakeArgs {
+    pub amount: Amount,
+}
+
+/// SlashOracle аргументы - списать залог оракула за нарушение
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct SlashOracleArgs {
+    pub oracle: AccountOwner,
+    pub amount: Amount,
+    pub reason: String,
+    /// Заодно отстранить оракула
+    pub suspend: bool,
+}
+
+/// Transfer аргументы - владелец передает паспорт, если это разрешено параметрами
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct TransferArgs {
+    pub token_id: TokenId,
+    /// Цепочка паспорта, если она не совпадает с текущей
+    pub target_chain: Option<ChainId>,
+    pub new_owner: AccountOwner,
+}
+
+/// GrantRole/RevokeRole аргументы
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct RoleArgs {
+    pub account: AccountOwner,
+    pub role: Role,
+}
+
+/// ProposeAdmin аргументы - первый шаг передачи прав администратора
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct ProposeAdminArgs {
+    /// Новый администратор; пусто - отменить предложение
+    pub candidate: Option<AccountOwner>,
+}
+
+/// Migrate аргументы - перенести паспорт на другую цепочку владельца
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+pub struct MigrateArgs {
+    pub token_id: TokenId,
+    pub destination: ChainId,
+}
+
 /// Все возможные операции контракта
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PassportOperation {
+    /// Выпуск паспорта. На цепочке-создателе выполняется сразу; на другой
+    /// цепочке отправляется туда на проверку (`RegisterMint`), возвращает
+    /// `Forwarded(цепочка-создатель)`, а паспорт появляется после одобрения.
     Mint(MintArgs),
     AddAchievement(AddAchievementArgs),
     IncreaseScore(IncreaseScoreArgs),
     UpdateAchievements(UpdateArgs),
     AddOracle(AddOracleArgs),
     RemoveOracle(RemoveOracleArgs),
+    Migrate(MigrateArgs),
+    Burn(BurnArgs),
+    Revoke(RevokeArgs),
+    Transfer(TransferArgs),
+    GrantRole(RoleArgs),
+    RevokeRole(RoleArgs),
+    ProposeAdmin(ProposeAdminArgs),
+    /// Предложенный администратор принимает права
+    AcceptAdmin,
+    SetPause(SetPauseArgs),
+    DisputeUpdate(DisputeArgs),
+    ResolveDispute(ResolveDisputeArgs),
+    /// Может вызвать кто угодно, когда окно оспаривания истекло
+    FinalizeUpdate(FinalizeUpdateArgs),
+    RegisterOracle(RegisterOracleArgs),
+    UnbondStake(UnbondStakeArgs),
+    /// Вернуть оракулу залог, задержка вывода которого истекла
+    WithdrawStake,
+    SlashOracle(SlashOracleArgs),
 }
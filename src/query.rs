@@ -1,15 +1,105 @@
 use std::sync::Arc;
 
 use async_graphql::{Error, Object, Result};
+use linera_base::data_types::Amount;
+use linera_base::identifiers::{AccountOwner, ChainId};
 
-use passport_nft::{Passport, PassportState, TokenId};
+use passport_nft::{
+    OracleRecord, Passport, PassportParameters, PassportState, PauseFlags, Role,
+    RoleChange, Tombstone, TokenId, UpdateProposal,
+};
 
 pub struct QueryRoot {
     pub state: Arc<PassportState>,
+    pub parameters: PassportParameters,
 }
 
 #[Object]
 impl QueryRoot {
+    /// Параметры приложения: политики выпуска и передачи, лимиты, самостоятельные заявления
+    async fn parameters(&self) -> &PassportParameters {
+        &self.parameters
+    }
+
+    /// Администратор приложения (ведется на цепочке-создателе)
+    async fn admin(&self) -> Option<AccountOwner> {
+        *self.state.admin.get()
+    }
+
+    /// Оракулы, которые сейчас могут обновлять паспорта: активные, с залогом не меньше
+    /// `minStake` или назначенные при создании (ведутся на цепочке-создателе)
+    async fn oracles(&self) -> Result<Vec<AccountOwner>> {
+        let staking = &self.parameters.staking;
+        Ok(self
+            .oracle_registry()
+            .await?
+            .into_iter()
+            .filter(|record| record.is_eligible(staking))
+            .map(|record| record.oracle)
+            .collect())
+    }
+
+    /// Запись реестра оракула
+    async fn oracle(&self, account: AccountOwner) -> Result<Option<OracleRecord>> {
+        self.state
+            .oracles
+            .get(&account)
+            .await
+            .map_err(|e| Error::new(format!("failed to read oracle: {e}")))
+    }
+
+    /// Весь реестр оракулов, включая отстраненных и выводящих залог
+    async fn oracle_registry(&self) -> Result<Vec<OracleRecord>> {
+        let mut records = Vec::new();
+        self.state
+            .oracles
+            .for_each_index_value(|_, record| {
+                records.push(record.into_owned());
+                Ok(())
+            })
+            .await
+            .map_err(|e| Error::new(format!("failed to iterate oracles: {e}")))?;
+        Ok(records)
+    }
+
+    /// Сумма списанных залогов; остается на счете приложения
+    async fn slashed_stake(&self) -> Amount {
+        *self.state.slashed_stake.get()
+    }
+
+    /// Предложенный администратор, еще не принявший права
+    async fn pending_admin(&self) -> Option<AccountOwner> {
+        *self.state.pending_admin.get()
+    }
+
+    /// Роли аккаунта; администратор обладает всеми ролями неявно
+    async fn roles(&self, account: AccountOwner) -> Result<Vec<Role>> {
+        Ok(self
+            .state
+            .roles
+            .get(&account)
+            .await
+            .map_err(|e| Error::new(format!("failed to read roles: {e}")))?
+            .unwrap_or_default())
+    }
+
+    /// Журнал изменений ролей, оракулов и администратора, от старых к новым
+    async fn role_log(&self, offset: Option<usize>, limit: Option<usize>) -> Result<Vec<RoleChange>> {
+        let count = self.state.role_log.count();
+        let start = offset.unwrap_or(0).min(count);
+        let end = limit.map_or(count, |limit| start.saturating_add(limit).min(count));
+        self.state
+            .role_log
+            .read(start..end)
+            .await
+            .map_err(|e| Error::new(format!("failed to read role log: {e}")))
+    }
+
+    /// Операции, приостановленные на этой цепочке
+    async fn paused(&self) -> PauseFlags {
+        *self.state.paused.get()
+    }
+
     async fn total_supply(&self) -> Result<u64> {
         Ok(*self.state.total_supply.get())
     }
@@ -22,6 +112,57 @@ impl QueryRoot {
             .map_err(|e| Error::new(format!("failed to read passport: {e}")))
     }
 
+    /// Цепочка, на которую паспорт был перенесен с этой цепочки
+    async fn migrated_to(&self, token_id: TokenId) -> Result<Option<ChainId>> {
+        self.state
+            .migrated_to
+            .get(&token_id)
+            .await
+            .map_err(|e| Error::new(format!("failed to read migration record: {e}")))
+    }
+
+    /// Запись об уничтоженном или отозванном паспорте
+    async fn tombstone(&self, token_id: TokenId) -> Result<Option<Tombstone>> {
+        self.state
+            .tombstones
+            .get(&token_id)
+            .await
+            .map_err(|e| Error::new(format!("failed to read tombstone: {e}")))
+    }
+
+    /// Отозванный модерацией паспорт владельца; такому владельцу закрыт новый выпуск
+    async fn revoked_passport(&self, owner: AccountOwner) -> Result<Option<TokenId>> {
+        self.state
+            .revoked_owners
+            .get(&owner)
+            .await
+            .map_err(|e| Error::new(format!("failed to read revoked owners: {e}")))
+    }
+
+    /// Предложения оракулов, ожидающие кворума. Истекшие предложения удаляются
+    /// при следующем голосе за паспорт, до этого их отличает `expiresAt`.
+    async fn pending_proposals(&self, token_id: Option<TokenId>) -> Result<Vec<UpdateProposal>> {
+        if let Some(token_id) = token_id {
+            return Ok(self
+                .state
+                .proposals
+                .get(&token_id)
+                .await
+                .map_err(|e| Error::new(format!("failed to read proposals: {e}")))?
+                .unwrap_or_default());
+        }
+        let mut proposals = Vec::new();
+        self.state
+            .proposals
+            .for_each_index_value(|_, entries| {
+                proposals.extend(entries.into_owned());
+                Ok(())
+            })
+            .await
+            .map_err(|e| Error::new(format!("failed to iterate proposals: {e}")))?;
+        Ok(proposals)
+    }
+
     async fn all_passports(&self) -> Result<Vec<Passport>> {
         let mut passports = Vec::new();
         self.state
@@ -34,4 +175,32 @@ impl QueryRoot {
             .map_err(|e| Error::new(format!("failed to iterate passports: {e}")))?;
         Ok(passports)
     }
+
+    /// Паспорта, у которых есть достижение `code`; `issuer` сужает выборку до выданных этим
+    /// аккаунтом или одобренных им в кворуме
+    async fn passports_with_achievement(
+        &self,
+        code: String,
+        issuer: Option<AccountOwner>,
+    ) -> Result<Vec<Passport>> {
+        let mut passports = Vec::new();
+        self.state
+            .passports
+            .for_each_index_value(|_, passport| {
+                let matches = passport.achievements.iter().any(|achievement| {
+                    achievement.code == code
+                        && issuer.map_or(true, |issuer| {
+                            achievement.issuer == issuer
+                                || achievement.approved_by.contains(&issuer)
+                        })
+                });
+                if matches {
+                    passports.push(passport.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .map_err(|e| Error::new(format!("failed to iterate passports: {e}")))?;
+        Ok(passports)
+    }
 }
@@ -3,7 +3,8 @@
 use std::sync::Arc;
 
 use async_graphql::{Context, EmptySubscription, Object, Request, Response, Schema};
-use linera_base::identifiers::AccountOwner;
+use linera_base::data_types::Amount;
+use linera_base::identifiers::{AccountOwner, ChainId};
 use linera_sdk::{
     linera_base_types::WithServiceAbi, service, views::View, Service, ServiceRuntime,
 };
@@ -11,8 +12,11 @@ use linera_sdk::{
 mod query;
 
 use passport_nft::{
-    AddAchievementArgs, AddOracleArgs, IncreaseScoreArgs, MintArgs, PassportNftAbi,
-    PassportOperation, PassportState, RemoveOracleArgs, TokenId, UpdateArgs,
+    AchievementInput, AddAchievementArgs, AddOracleArgs, BurnArgs, IncreaseScoreArgs, MigrateArgs,
+    MintArgs, PassportNftAbi, PassportOperation, PassportParameters, PassportState,
+    PauseFlags, ProposeAdminArgs, RemoveOracleArgs, RevocationReason, RevokeArgs, Role, RoleArgs, TokenId,
+    SetPauseArgs, TransferArgs, UpdateArgs, DisputeArgs, FinalizeUpdateArgs, ResolveDisputeArgs,
+    RegisterOracleArgs, SlashOracleArgs, UnbondStakeArgs,
 };
 
 pub struct PassportService {
@@ -27,7 +31,7 @@ impl WithServiceAbi for PassportService {
 }
 
 impl Service for PassportService {
-    type Parameters = ();
+    type Parameters = PassportParameters;
 
     async fn new(runtime: ServiceRuntime<Self>) -> Self {
         let state = match PassportState::load(runtime.root_view_storage_context()).await {
@@ -49,6 +53,7 @@ impl Service for PassportService {
         let schema = Schema::build(
             query::QueryRoot {
                 state: self.state.clone(),
+                parameters: self.runtime.application_parameters(),
             },
             MutationRoot,
             EmptySubscription,
@@ -81,6 +86,7 @@ impl MutationRoot {
         metadata_uri: String,
         image_uri: String,
         content_hash: String,
+        owner: Option<AccountOwner>,
     ) -> [u8; 0] {
         if let Some(runtime) = get_runtime(ctx) {
             let operation = PassportOperation::Mint(MintArgs {
@@ -88,6 +94,7 @@ impl MutationRoot {
                 metadata_uri,
                 image_uri,
                 content_hash,
+                owner,
             });
             runtime.schedule_operation(&operation);
         }
@@ -98,21 +105,35 @@ impl MutationRoot {
         &self,
         ctx: &Context<'_>,
         token_id: TokenId,
-        achievement: String,
+        code: String,
+        evidence_hash: Option<String>,
+        target_chain: Option<ChainId>,
     ) -> [u8; 0] {
         if let Some(runtime) = get_runtime(ctx) {
             let operation = PassportOperation::AddAchievement(AddAchievementArgs {
                 token_id,
-                achievement,
+                target_chain,
+                code,
+                evidence_hash,
             });
             runtime.schedule_operation(&operation);
         }
         []
     }
 
-    async fn increase_score(&self, ctx: &Context<'_>, token_id: TokenId, amount: u64) -> [u8; 0] {
+    async fn increase_score(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        amount: u64,
+        target_chain: Option<ChainId>,
+    ) -> [u8; 0] {
         if let Some(runtime) = get_runtime(ctx) {
-            let operation = PassportOperation::IncreaseScore(IncreaseScoreArgs { token_id, amount });
+            let operation = PassportOperation::IncreaseScore(IncreaseScoreArgs {
+                token_id,
+                target_chain,
+                amount,
+            });
             runtime.schedule_operation(&operation);
         }
         []
@@ -122,12 +143,16 @@ impl MutationRoot {
         &self,
         ctx: &Context<'_>,
         token_id: TokenId,
-        new_achievements: Vec<String>,
+        target_chain: Option<ChainId>,
+        expected_revision: u64,
+        new_achievements: Vec<AchievementInput>,
         score_increase: u64,
     ) -> [u8; 0] {
         if let Some(runtime) = get_runtime(ctx) {
             let operation = PassportOperation::UpdateAchievements(UpdateArgs {
                 token_id,
+                target_chain,
+                expected_revision,
                 new_achievements,
                 score_increase,
             });
@@ -144,6 +169,74 @@ impl MutationRoot {
         []
     }
 
+    async fn migrate(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        destination: ChainId,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::Migrate(MigrateArgs {
+                token_id,
+                destination,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn transfer(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+        new_owner: AccountOwner,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::Transfer(TransferArgs {
+                token_id,
+                target_chain,
+                new_owner,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn burn(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::Burn(BurnArgs {
+                token_id,
+                target_chain,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn revoke(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+        reason: RevocationReason,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::Revoke(RevokeArgs {
+                token_id,
+                target_chain,
+                reason,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
     async fn remove_oracle(&self, ctx: &Context<'_>, oracle: AccountOwner) -> [u8; 0] {
         if let Some(runtime) = get_runtime(ctx) {
             let operation = PassportOperation::RemoveOracle(RemoveOracleArgs { oracle });
@@ -151,4 +244,148 @@ impl MutationRoot {
         }
         []
     }
+
+    async fn register_oracle(&self, ctx: &Context<'_>, stake: Amount) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::RegisterOracle(RegisterOracleArgs { stake });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn unbond_stake(&self, ctx: &Context<'_>, amount: Amount) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::UnbondStake(UnbondStakeArgs { amount });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn withdraw_stake(&self, ctx: &Context<'_>) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            runtime.schedule_operation(&PassportOperation::WithdrawStake);
+        }
+        []
+    }
+
+    async fn slash_oracle(
+        &self,
+        ctx: &Context<'_>,
+        oracle: AccountOwner,
+        amount: Amount,
+        reason: String,
+        suspend: bool,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::SlashOracle(SlashOracleArgs {
+                oracle,
+                amount,
+                reason,
+                suspend,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn grant_role(&self, ctx: &Context<'_>, account: AccountOwner, role: Role) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::GrantRole(RoleArgs { account, role });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn revoke_role(&self, ctx: &Context<'_>, account: AccountOwner, role: Role) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::RevokeRole(RoleArgs { account, role });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    /// Без `candidate` отменяет текущее предложение
+    async fn propose_admin(&self, ctx: &Context<'_>, candidate: Option<AccountOwner>) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::ProposeAdmin(ProposeAdminArgs { candidate });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    /// Оспорить обновление оракула на проверке
+    async fn dispute_update(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+        reason: String,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::DisputeUpdate(DisputeArgs {
+                token_id,
+                target_chain,
+                reason,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn resolve_dispute(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+        accept: bool,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::ResolveDispute(ResolveDisputeArgs {
+                token_id,
+                target_chain,
+                accept,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn finalize_update(
+        &self,
+        ctx: &Context<'_>,
+        token_id: TokenId,
+        target_chain: Option<ChainId>,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::FinalizeUpdate(FinalizeUpdateArgs {
+                token_id,
+                target_chain,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn set_pause(
+        &self,
+        ctx: &Context<'_>,
+        target_chain: Option<ChainId>,
+        flags: PauseFlags,
+    ) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            let operation = PassportOperation::SetPause(SetPauseArgs {
+                target_chain,
+                flags,
+            });
+            runtime.schedule_operation(&operation);
+        }
+        []
+    }
+
+    async fn accept_admin(&self, ctx: &Context<'_>) -> [u8; 0] {
+        if let Some(runtime) = get_runtime(ctx) {
+            runtime.schedule_operation(&PassportOperation::AcceptAdmin);
+        }
+        []
+    }
 }
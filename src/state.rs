@@ -1,7 +1,10 @@
-use linera_base::identifiers::AccountOwner;
-use linera_sdk::views::{linera_views, MapView, RegisterView, SetView, RootView, ViewStorageContext};
+use linera_base::data_types::Amount;
+use linera_base::identifiers::{AccountOwner, ChainId};
+use linera_sdk::views::{linera_views, LogView, MapView, RegisterView, RootView, ViewStorageContext};
 
-use crate::{Passport, TokenId};
+use crate::{
+    OracleRecord, Passport, PauseFlags, Role, RoleChange, Tombstone, TokenId, UpdateProposal,
+};
 
 /// Основное состояние приложения Passport NFT
 #[derive(RootView)]
@@ -13,9 +16,29 @@ pub struct PassportState {
     pub total_supply: RegisterView<u64>,
     /// Сопоставление владельцев их паспорту (для ограничения 1 паспорт на владельца)
     pub owner_index: MapView<AccountOwner, TokenId>,
-    /// Список авторизованных оракулов (могут обновлять паспорта)
-    pub authorized_oracles: SetView<AccountOwner>,
-    /// SECURITY FIX: Administrator of the application (can manage oracles)
-    /// Set during instantiation to the first signer
+    /// Реестр оракулов; обновлять паспорта могут активные оракулы с достаточным залогом
+    pub oracles: MapView<AccountOwner, OracleRecord>,
+    /// Сумма списанных залогов; остается на счете приложения
+    pub slashed_stake: RegisterView<Amount>,
+    /// SECURITY FIX: Administrator of the application (holds every role)
+    /// Set during instantiation, changed only through propose/accept
     pub admin: RegisterView<Option<AccountOwner>>,
+    /// Администратор, предложенный текущим и еще не принявший права
+    pub pending_admin: RegisterView<Option<AccountOwner>>,
+    /// Роли аккаунтов, кроме администратора
+    pub roles: MapView<AccountOwner, Vec<Role>>,
+    /// Журнал всех изменений ролей, оракулов и администратора
+    pub role_log: LogView<RoleChange>,
+    /// Предложения оракулов, ожидающие кворума (на цепочке-создателе)
+    pub proposals: MapView<TokenId, Vec<UpdateProposal>>,
+    /// Приостановленные на этой цепочке операции
+    pub paused: RegisterView<PauseFlags>,
+    /// Паспорта, перенесенные с этой цепочки: `token_id` -> цепочка назначения
+    pub migrated_to: MapView<TokenId, ChainId>,
+    /// Уничтоженные и отозванные паспорта; их `token_id` нельзя выпустить снова.
+    /// На цепочке-создателе собираются записи со всех цепочек.
+    pub tombstones: MapView<TokenId, Tombstone>,
+    /// Владельцы отозванных модерацией паспортов и их `token_id`; им нельзя
+    /// выпустить, перенести к себе или получить паспорт
+    pub revoked_owners: MapView<AccountOwner, TokenId>,
 }
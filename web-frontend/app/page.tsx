@@ -23,7 +23,9 @@ const GET_ALL_PASSPORTS = gql`
       owner
       ownerChain
       score
-      achievements
+      pendingScore
+      pendingUpdate { challengeEndsAt dispute { reason } }
+      achievements { code points awardedAt issuer evidenceHash }
     }
   }
 `
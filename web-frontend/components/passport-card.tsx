@@ -2,13 +2,16 @@
 
 import { Button } from "@/components/ui/button"
 import { Sparkles } from "lucide-react"
+import type { Achievement } from "@/types/passport"
 
 interface Passport {
   tokenId: { id: number[] }
   owner: string
   ownerChain: string
   score: number
-  achievements: string[]
+  pendingScore?: number
+  pendingUpdate?: { challengeEndsAt: string; dispute: { reason: string } | null } | null
+  achievements: Achievement[]
 }
 
 interface PassportCardProps {
@@ -50,6 +53,11 @@ export function PassportCard({ passport, onUpdate }: PassportCardProps) {
         <div className="mb-6">
           <p className="text-xs font-light uppercase tracking-widest text-stone-500">Reputation Score</p>
           <p className="text-5xl font-bold text-red-600">{passport.score}</p>
+          {passport.pendingUpdate && (
+            <p className="text-xs text-stone-500">
+              {passport.pendingUpdate.dispute ? "Disputed" : "Under review"}: {passport.pendingScore}
+            </p>
+          )}
         </div>
 
         {/* Achievements */}
@@ -61,7 +69,10 @@ export function PassportCard({ passport, onUpdate }: PassportCardProps) {
             {passport.achievements && passport.achievements.length > 0 ? (
               <div className="space-y-1">
                 {passport.achievements.map((ach, idx) => (
-                  <p key={idx} className="text-xs text-stone-600">• {ach}</p>
+                  <p key={idx} className="text-xs text-stone-600" title={`Issued by ${ach.issuer}`}>
+                    • {ach.code}
+                    {ach.points > 0 ? ` (+${ach.points})` : ""}
+                  </p>
                 ))}
               </div>
             ) : (
@@ -14,9 +14,17 @@ export interface MintPassportParams {
   contentHash: string
 }
 
+export interface AchievementInput {
+  code: string
+  points: number
+  evidenceHash?: string | null
+}
+
 export interface UpdateAchievementsParams {
   tokenId: TokenIdInput
-  newAchievements: string[]
+  targetChain?: string | null
+  expectedRevision: number
+  newAchievements: AchievementInput[]
   scoreIncrease: number
 }
 
@@ -88,11 +96,15 @@ export async function updateAchievements(
   const mutation = `
     mutation UpdateAchievements(
       $tokenId: TokenIdInput!
-      $newAchievements: [String!]!
+      $targetChain: ChainId
+      $expectedRevision: Int!
+      $newAchievements: [AchievementInput!]!
       $scoreIncrease: Int!
     ) {
       updateAchievements(
         tokenId: $tokenId
+        targetChain: $targetChain
+        expectedRevision: $expectedRevision
         newAchievements: $newAchievements
         scoreIncrease: $scoreIncrease
       )
@@ -101,6 +113,8 @@ export async function updateAchievements(
 
   const variables = {
     tokenId: params.tokenId,
+    targetChain: params.targetChain ?? null,
+    expectedRevision: params.expectedRevision,
     newAchievements: params.newAchievements,
     scoreIncrease: params.scoreIncrease,
   }
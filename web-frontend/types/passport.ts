@@ -1,9 +1,17 @@
+export interface Achievement {
+  code: string
+  points: number
+  awardedAt: string
+  issuer: string
+  evidenceHash: string | null
+}
+
 export interface PassportData {
   tokenId: string
   owner: string
   chainId: string
   score: number
-  achievements: string[]
+  achievements: Achievement[]
 }
 
 export interface PassportGraphQLData {
@@ -13,7 +21,7 @@ export interface PassportGraphQLData {
   owner: string
   ownerChain: string
   score: number | null
-  achievements: string[] | null
+  achievements: Achievement[] | null
 }
 
 export interface LeaderboardEntry {